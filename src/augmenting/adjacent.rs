@@ -1,6 +1,6 @@
 use crate::error::Error;
 use crate::itertools::{tee::tee, map::map, zip::zip};
-use crate::windowing::windowed::windowed;
+use crate::windowing::windowed::{windowed, WindowFill};
 use crate::utils::{any1, generate_okok_iterator};
 
 use crate::itertools::chain::chain; 
@@ -26,7 +26,7 @@ T: Clone + 'static
     let selected = chain(input);
     
 
-    let adjacent_to_selected = map(windowed(selected, 2 * distance + 1, 1), any1);
+    let adjacent_to_selected = map(windowed(selected, 2 * distance + 1, 1, WindowFill::Drop), any1);
     // println!("adjacent_to_selected={:?}", adjacent_to_selected);
 
     let ret0 = zip(adjacent_to_selected, i2);
@@ -0,0 +1,287 @@
+use std::fs::{self, File};
+use std::io::{self, BufRead, BufReader, BufWriter, Write};
+use std::path::{Path, PathBuf};
+use std::rc::Rc;
+use std::sync::atomic::{AtomicUsize, Ordering};
+
+use crate::combining::merge::merge;
+use crate::error::{self, Error};
+use crate::grouping::chunked::chunked;
+
+/// How `external_sort` writes items to its run files and reads them back.
+pub trait SpillCodec<T> {
+    fn serialize(&self, item: &T, writer: &mut dyn Write) -> io::Result<()>;
+
+    /// Reads the next item, or `Ok(None)` at the end of the run.
+    fn deserialize(&self, reader: &mut dyn BufRead) -> io::Result<Option<T>>;
+}
+
+// distinguishes the run files of concurrent sorts sharing a tmp_dir
+static NEXT_SORT_ID: AtomicUsize = AtomicUsize::new(0);
+
+fn map_io_error(err: io::Error, path: &Path) -> Error {
+    error::io_error(format!("[external_sort] {}: {}", path.display(), err))
+}
+
+// A sorted run spilled to disk; the file is removed once the run is dropped.
+struct RunReader<T, C> {
+    reader: BufReader<File>,
+    path: PathBuf,
+    codec: Rc<C>,
+    iter_finished: bool,
+    _marker: std::marker::PhantomData<T>
+}
+
+impl<T, C> Iterator for RunReader<T, C>
+where C: SpillCodec<T>
+{
+    type Item = Result<T,Error>;
+
+    fn next(&mut self) -> Option<Self::Item> {
+        if self.iter_finished {
+            return None;
+        }
+
+        match self.codec.deserialize(&mut self.reader) {
+            Ok(Some(v)) => Some(Ok(v)),
+            Ok(None) => {
+                self.iter_finished = true;
+                None
+            },
+            Err(err) => {
+                self.iter_finished = true;
+                Some(Err(map_io_error(err, &self.path)))
+            }
+        }
+    }
+}
+
+impl<T, C> Drop for RunReader<T, C> {
+    fn drop(&mut self) {
+        let _ = fs::remove_file(&self.path);
+    }
+}
+
+fn spill<T, C>(run: &[T], path: PathBuf, codec: &Rc<C>) -> Result<RunReader<T, C>, Error>
+where C: SpillCodec<T>
+{
+    let write_run = || -> io::Result<()> {
+        let mut writer = BufWriter::new(File::create(&path)?);
+        for item in run.iter() {
+            codec.serialize(item, &mut writer)?;
+        }
+        writer.flush()
+    };
+
+    if let Err(err) = write_run() {
+        let _ = fs::remove_file(&path);
+        return Err(map_io_error(err, &path));
+    }
+
+    match File::open(&path) {
+        Ok(file) => Ok(RunReader {
+            reader: BufReader::new(file),
+            path,
+            codec: Rc::clone(codec),
+            iter_finished: false,
+            _marker: std::marker::PhantomData
+        }),
+        Err(err) => {
+            let _ = fs::remove_file(&path);
+            Err(map_io_error(err, &path))
+        }
+    }
+}
+
+type Runs<T> = Vec<Box<dyn Iterator<Item = Result<T,Error>>>>;
+
+struct ExternalSort<T, K, C> {
+    iter: Option<Box<dyn Iterator<Item = Result<T,Error>>>>,
+    key: fn(&T) -> K,
+    chunk_size: usize,
+    tmp_dir: PathBuf,
+    codec: Rc<C>,
+    merged: Option<Box<dyn Iterator<Item = Result<T,Error>>>>,
+    iter_finished: bool
+}
+
+impl<T, K, C> ExternalSort<T, K, C>
+where
+T: 'static,
+K: Ord + 'static,
+C: SpillCodec<T> + 'static
+{
+    // Splits the input into sorted runs; every run but the last is spilled to disk.
+    fn build_runs(&mut self, iter: Box<dyn Iterator<Item = Result<T,Error>>>) -> Result<Runs<T>, Error> {
+        let sort_id = NEXT_SORT_ID.fetch_add(1, Ordering::Relaxed);
+        let mut runs: Runs<T> = Vec::new();
+        let mut last_run: Option<Vec<T>> = None;
+
+        for chunk in chunked(iter, self.chunk_size, false) {
+            let mut run = chunk?.into_iter().collect::<Result<Vec<T>,Error>>()?;
+            run.sort_by_key(self.key);
+
+            if let Some(prev) = last_run.replace(run) {
+                let path = self.tmp_dir.join(format!("more-itertools-sort-{}-{}-{}.run", std::process::id(), sort_id, runs.len()));
+                runs.push(Box::new(spill(&prev, path, &self.codec)?));
+            }
+        }
+
+        if let Some(run) = last_run {
+            runs.push(Box::new(run.into_iter().map(Ok)));
+        }
+        Ok(runs)
+    }
+}
+
+impl<T, K, C> Iterator for ExternalSort<T, K, C>
+where
+T: 'static,
+K: Ord + 'static,
+C: SpillCodec<T> + 'static
+{
+    type Item = Result<T,Error>;
+
+    fn next(&mut self) -> Option<Self::Item> {
+        if self.iter_finished {
+            return None;
+        }
+
+        if self.chunk_size == 0 {
+            self.iter_finished = true;
+            return Some(Err(error::value_error("[external_sort] chunk_size should not be 0".to_string())));
+        }
+
+        if let Some(iter) = self.iter.take() {
+            match self.build_runs(iter) {
+                Ok(runs) => { self.merged = Some(merge(runs, self.key, false)); },
+                Err(err) => {
+                    self.iter_finished = true;
+                    return Some(Err(err));
+                }
+            }
+        }
+
+        let ret = self.merged.as_mut().and_then(|merged| merged.next());
+        if !matches!(ret, Some(Ok(_))) {
+            self.iter_finished = true;
+            // drop the remaining runs now so that their files are removed
+            self.merged = None;
+        }
+        ret
+    }
+}
+
+/// Sorts a stream that may not fit in memory, stably by `key`.
+///
+/// The input is cut into chunks of `chunk_size` items; each chunk is sorted
+/// and written to a run file in `tmp_dir` through `codec`, then the runs are
+/// merged lazily. Run files are removed once they are consumed or the
+/// iterator is dropped. I/O failures are reported as `Kind::IOError`.
+pub fn external_sort<T, K, C>(iter: Box<dyn Iterator<Item = Result<T,Error>>>,
+                              key: fn(&T) -> K,
+                              chunk_size: usize,
+                              tmp_dir: &Path,
+                              codec: C) -> Box<dyn Iterator<Item = Result<T,Error>>>
+where
+T: 'static,
+K: Ord + 'static,
+C: SpillCodec<T> + 'static
+{
+    Box::new(ExternalSort {
+        iter: Some(iter),
+        key,
+        chunk_size,
+        tmp_dir: tmp_dir.to_path_buf(),
+        codec: Rc::new(codec),
+        merged: None,
+        iter_finished: false
+    })
+}
+
+#[cfg(test)]
+mod tests {
+    use crate::utils::{extract_value_from_result_vec, generate_okok_iterator, generate_okokerr_iterator};
+
+    use super::*;
+
+    struct PairCodec;
+
+    impl SpillCodec<(i32, char)> for PairCodec {
+        fn serialize(&self, item: &(i32, char), writer: &mut dyn Write) -> io::Result<()> {
+            writer.write_all(&item.0.to_le_bytes())?;
+            writer.write_all(&(item.1 as u32).to_le_bytes())
+        }
+
+        fn deserialize(&self, reader: &mut dyn BufRead) -> io::Result<Option<(i32, char)>> {
+            if reader.fill_buf()?.is_empty() {
+                return Ok(None);
+            }
+            let mut buf = [0u8; 8];
+            reader.read_exact(&mut buf)?;
+            let k = i32::from_le_bytes(buf[0..4].try_into().unwrap());
+            let c = char::from_u32(u32::from_le_bytes(buf[4..8].try_into().unwrap()));
+            match c {
+                None => Err(io::Error::new(io::ErrorKind::InvalidData, "bad char")),
+                Some(c) => Ok(Some((k, c)))
+            }
+        }
+    }
+
+    fn tmp_dir(name: &str) -> PathBuf {
+        let dir = std::env::temp_dir().join(format!("more-itertools-test-{}-{}", std::process::id(), name));
+        fs::create_dir_all(&dir).unwrap();
+        dir
+    }
+
+    fn file_count(dir: &Path) -> usize {
+        fs::read_dir(dir).unwrap().count()
+    }
+
+    #[test]
+    fn test1() {
+        let dir = tmp_dir("external_sort_test1");
+        let v = vec![(5, 'a'), (3, 'b'), (9, 'c'), (1, 'd'), (3, 'e'), (7, 'f'), (0, 'g'), (3, 'h')];
+        let mut ret = external_sort(generate_okok_iterator(v.clone()), |x| x.0, 3, &dir, PairCodec);
+
+        assert_eq!(Some(Ok((0, 'g'))), ret.next());
+        assert_eq!(2, file_count(&dir));
+
+        let mut expected = v;
+        expected.sort_by_key(|x| x.0);
+        assert_eq!(expected[1..].to_vec(), extract_value_from_result_vec(ret.collect::<Vec<_>>()).0);
+        assert_eq!(0, file_count(&dir));
+        fs::remove_dir(&dir).unwrap();
+    }
+
+    #[test]
+    fn test2_in_memory() {
+        let dir = tmp_dir("external_sort_test2");
+        let ret = external_sort(generate_okok_iterator(vec![(2, 'a'), (1, 'b')]), |x| x.0, 10, &dir, PairCodec);
+        assert_eq!(vec![(1, 'b'), (2, 'a')], extract_value_from_result_vec(ret.collect::<Vec<_>>()).0);
+
+        let ret = external_sort(generate_okok_iterator(Vec::<(i32, char)>::new()), |x| x.0, 10, &dir, PairCodec);
+        assert_eq!(0, ret.count());
+        fs::remove_dir(&dir).unwrap();
+    }
+
+    #[test]
+    fn test3_error() {
+        let dir = tmp_dir("external_sort_test3");
+        let mut ret = external_sort(generate_okok_iterator(vec![(1, 'a')]), |x| x.0, 0, &dir, PairCodec);
+        assert_eq!(error::Kind::ValueError, ret.next().unwrap().err().unwrap().kind());
+        assert_eq!(None, ret.next());
+
+        let mut ret = external_sort(generate_okokerr_iterator(vec![(2, 'a'), (1, 'b'), (0, 'c')], error::overflow_error("[test]".to_string())),
+                                    |x| x.0, 1, &dir, PairCodec);
+        assert_eq!(error::Kind::OverflowError, ret.next().unwrap().err().unwrap().kind());
+        assert_eq!(None, ret.next());
+        assert_eq!(0, file_count(&dir));
+
+        let missing = dir.join("missing");
+        let mut ret = external_sort(generate_okok_iterator(vec![(2, 'a'), (1, 'b')]), |x| x.0, 1, &missing, PairCodec);
+        assert_eq!(error::Kind::IOError, ret.next().unwrap().err().unwrap().kind());
+        assert_eq!(None, ret.next());
+        fs::remove_dir(&dir).unwrap();
+    }
+}
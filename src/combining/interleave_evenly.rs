@@ -0,0 +1,150 @@
+use std::cmp::Reverse;
+use std::collections::VecDeque;
+
+use crate::error::{self, Error};
+
+struct InterleaveEvenly<T> {
+    iter_vec: Vec<Box<dyn Iterator<Item = Result<T,Error>>>>,
+    lengths: Vec<usize>,
+    // input indices sorted by length, longest first; order[0] is the primary input
+    order: Vec<usize>,
+    // Bresenham error terms of the secondary inputs order[1..]
+    errors: Vec<i128>,
+    // inputs to read from before computing the next step
+    pending: VecDeque<usize>,
+    to_yield: usize,
+    initialized: bool,
+    iter_finished: bool
+}
+
+impl<T> InterleaveEvenly<T> {
+    fn init(&mut self) -> Result<(), Error> {
+        if self.lengths.len() != self.iter_vec.len() {
+            return Err(error::value_error("[interleave_evenly] must have one length per input".to_string()));
+        }
+
+        self.order = (0..self.lengths.len()).collect();
+        let lengths = &self.lengths;
+        self.order.sort_by_key(|i| Reverse(lengths[*i]));
+        self.to_yield = lengths.iter().sum();
+
+        if let Some(primary) = self.order.first() {
+            let start = (lengths[*primary] / lengths.len()) as i128;
+            self.errors = vec![start; lengths.len() - 1];
+        }
+        Ok(())
+    }
+
+    fn schedule_step(&mut self) {
+        let primary = self.order[0];
+        self.pending.push_back(primary);
+        for (k, e) in self.errors.iter_mut().enumerate() {
+            let secondary = self.order[k + 1];
+            *e -= self.lengths[secondary] as i128;
+            if *e < 0 {
+                self.pending.push_back(secondary);
+                *e += self.lengths[primary] as i128;
+            }
+        }
+    }
+}
+
+impl<T> Iterator for InterleaveEvenly<T> {
+    type Item = Result<T,Error>;
+
+    fn next(&mut self) -> Option<Self::Item> {
+        if self.iter_finished {
+            return None;
+        }
+
+        if !self.initialized {
+            self.initialized = true;
+            if let Err(err) = self.init() {
+                self.iter_finished = true;
+                return Some(Err(err));
+            }
+        }
+
+        if self.to_yield == 0 {
+            self.iter_finished = true;
+            return None;
+        }
+
+        if self.pending.is_empty() {
+            self.schedule_step();
+        }
+
+        let i = self.pending.pop_front().unwrap();
+        self.to_yield -= 1;
+        match self.iter_vec[i].next() {
+            Some(Ok(v)) => Some(Ok(v)),
+            Some(Err(err)) => {
+                self.iter_finished = true;
+                Some(Err(err))
+            },
+            None => {
+                self.iter_finished = true;
+                Some(Err(error::value_error(format!("[interleave_evenly] input {} is shorter than its length {}", i, self.lengths[i]))))
+            }
+        }
+    }
+}
+
+/// https://more-itertools.readthedocs.io/en/v10.2.0/api.html#more_itertools.interleave_evenly
+///
+/// Interleaves inputs of different `lengths` so that the items of each one are
+/// spread as evenly as possible over the output.
+pub fn interleave_evenly<T>(iter_vec: Vec<Box<dyn Iterator<Item = Result<T,Error>>>>, lengths: Vec<usize>) -> Box<dyn Iterator<Item = Result<T,Error>>>
+where T: 'static
+{
+    Box::new(InterleaveEvenly {
+        iter_vec,
+        lengths,
+        order: Vec::new(),
+        errors: Vec::new(),
+        pending: VecDeque::new(),
+        to_yield: 0,
+        initialized: false,
+        iter_finished: false
+    })
+}
+
+#[cfg(test)]
+mod tests {
+    use crate::utils::{extract_value_from_result_vec, generate_okok_iterator, generate_okokerr_iterator};
+
+    use super::*;
+
+    #[test]
+    fn test1() {
+        let v = vec![generate_okok_iterator(vec![1, 2, 3, 4, 5]), generate_okok_iterator(vec![10, 20])];
+        let ret = interleave_evenly(v, vec![5, 2]);
+        assert_eq!(vec![1, 2, 10, 3, 4, 20, 5], extract_value_from_result_vec(ret.collect::<Vec<_>>()).0);
+    }
+
+    #[test]
+    fn test2() {
+        let v = vec![generate_okok_iterator(vec![1, 2, 3]),
+                    generate_okok_iterator(vec![4, 5, 6, 7, 8, 9]),
+                    generate_okok_iterator(vec![10, 11])];
+        let ret = interleave_evenly(v, vec![3, 6, 2]);
+        assert_eq!(vec![4, 1, 5, 10, 6, 2, 7, 8, 3, 11, 9], extract_value_from_result_vec(ret.collect::<Vec<_>>()).0);
+    }
+
+    #[test]
+    fn test3_error() {
+        let mut ret = interleave_evenly(vec![generate_okok_iterator(vec![1])], vec![1, 2]);
+        assert_eq!(error::Kind::ValueError, ret.next().unwrap().err().unwrap().kind());
+        assert_eq!(None, ret.next());
+
+        let mut ret = interleave_evenly(vec![generate_okok_iterator(vec![1])], vec![2]);
+        assert_eq!(Some(Ok(1)), ret.next());
+        assert_eq!(error::Kind::ValueError, ret.next().unwrap().err().unwrap().kind());
+        assert_eq!(None, ret.next());
+
+        let mut ret = interleave_evenly(vec![generate_okokerr_iterator(vec![1], error::overflow_error("[test]".to_string()))], vec![2]);
+        assert_eq!(Some(Ok(1)), ret.next());
+        assert_eq!(error::Kind::OverflowError, ret.next().unwrap().err().unwrap().kind());
+        assert_eq!(None, ret.next());
+    }
+}
@@ -0,0 +1,422 @@
+use std::collections::{HashMap, VecDeque};
+use std::hash::Hash;
+
+use crate::error::{self, Error};
+
+/// Rows of a join: `(Some(l), Some(r))` for a match, `None` on the side without one.
+pub type JoinIter<L, R> = Box<dyn Iterator<Item = Result<(Option<L>, Option<R>),Error>>>;
+
+#[derive(Clone, Copy, Debug, PartialEq, Eq)]
+pub enum JoinKind {
+    Inner,
+    Left,
+    Right,
+    FullOuter
+}
+
+impl JoinKind {
+    fn keeps_left(&self) -> bool {
+        matches!(self, JoinKind::Left | JoinKind::FullOuter)
+    }
+
+    fn keeps_right(&self) -> bool {
+        matches!(self, JoinKind::Right | JoinKind::FullOuter)
+    }
+}
+
+struct HashJoin<L, R, K> {
+    left: Box<dyn Iterator<Item = Result<L,Error>>>,
+    right: Option<Box<dyn Iterator<Item = Result<R,Error>>>>,
+    left_key: fn(&L) -> K,
+    right_key: fn(&R) -> K,
+    kind: JoinKind,
+    // the materialized right side, in input order
+    right_rows: Vec<R>,
+    right_matched: Vec<bool>,
+    index: HashMap<K, Vec<usize>>,
+    buf: VecDeque<(Option<L>, Option<R>)>,
+    unmatched_cursor: usize,
+    left_finished: bool,
+    iter_finished: bool
+}
+
+impl<L, R, K> HashJoin<L, R, K>
+where
+L: Clone,
+R: Clone,
+K: Hash + Eq
+{
+    fn build(&mut self) -> Result<(), Error> {
+        if let Some(right) = self.right.take() {
+            for item in right {
+                let item = item?;
+                self.index.entry((self.right_key)(&item)).or_default().push(self.right_rows.len());
+                self.right_rows.push(item);
+            }
+            self.right_matched = vec![false; self.right_rows.len()];
+        }
+        Ok(())
+    }
+}
+
+impl<L, R, K> Iterator for HashJoin<L, R, K>
+where
+L: Clone,
+R: Clone,
+K: Hash + Eq
+{
+    type Item = Result<(Option<L>, Option<R>),Error>;
+
+    fn next(&mut self) -> Option<Self::Item> {
+        if self.iter_finished {
+            return None;
+        }
+
+        if let Err(err) = self.build() {
+            self.iter_finished = true;
+            return Some(Err(err));
+        }
+
+        loop {
+            if let Some(row) = self.buf.pop_front() {
+                return Some(Ok(row));
+            }
+
+            if self.left_finished {
+                break;
+            }
+
+            match self.left.next() {
+                None => { self.left_finished = true; },
+                Some(Err(err)) => {
+                    self.iter_finished = true;
+                    return Some(Err(err));
+                },
+                Some(Ok(l)) => {
+                    match self.index.get(&(self.left_key)(&l)) {
+                        None => {
+                            if self.kind.keeps_left() {
+                                return Some(Ok((Some(l), None)));
+                            }
+                        },
+                        Some(rows) => {
+                            for i in rows.iter() {
+                                self.right_matched[*i] = true;
+                                self.buf.push_back((Some(l.clone()), Some(self.right_rows[*i].clone())));
+                            }
+                        }
+                    }
+                }
+            }
+        }
+
+        if self.kind.keeps_right() {
+            while self.unmatched_cursor < self.right_rows.len() {
+                let i = self.unmatched_cursor;
+                self.unmatched_cursor += 1;
+                if !self.right_matched[i] {
+                    return Some(Ok((None, Some(self.right_rows[i].clone()))));
+                }
+            }
+        }
+
+        self.iter_finished = true;
+        None
+    }
+}
+
+/// Joins two inputs on equal keys by hashing the whole `right` input, then
+/// streaming `left`. Rows come in `left` order; for right and full outer joins
+/// the unmatched `right` rows follow, in their input order.
+pub fn hash_join<L, R, K>(left: Box<dyn Iterator<Item = Result<L,Error>>>,
+                          right: Box<dyn Iterator<Item = Result<R,Error>>>,
+                          left_key: fn(&L) -> K,
+                          right_key: fn(&R) -> K,
+                          kind: JoinKind) -> JoinIter<L, R>
+where
+L: Clone + 'static,
+R: Clone + 'static,
+K: Hash + Eq + 'static
+{
+    Box::new(HashJoin {
+        left,
+        right: Some(right),
+        left_key,
+        right_key,
+        kind,
+        right_rows: Vec::new(),
+        right_matched: Vec::new(),
+        index: HashMap::new(),
+        buf: VecDeque::new(),
+        unmatched_cursor: 0,
+        left_finished: false,
+        iter_finished: false
+    })
+}
+
+struct SortMergeJoin<L, R, K> {
+    left: Box<dyn Iterator<Item = Result<L,Error>>>,
+    right: Box<dyn Iterator<Item = Result<R,Error>>>,
+    left_key: fn(&L) -> K,
+    right_key: fn(&R) -> K,
+    kind: JoinKind,
+    left_head: Option<(K, L)>,
+    right_head: Option<(K, R)>,
+    initialized: bool,
+    buf: VecDeque<(Option<L>, Option<R>)>,
+    // an error read while filling `buf`, yielded once it is drained
+    error: Option<Error>,
+    iter_finished: bool
+}
+
+impl<L, R, K> SortMergeJoin<L, R, K>
+where
+L: Clone,
+R: Clone,
+K: Ord
+{
+    fn pull_left(&mut self, prev: Option<&K>) -> Result<Option<(K, L)>, Error> {
+        match self.left.next() {
+            None => Ok(None),
+            Some(Err(err)) => Err(err),
+            Some(Ok(v)) => {
+                let k = (self.left_key)(&v);
+                if prev.is_some_and(|p| k < *p) {
+                    return Err(error::value_error("[sort_merge_join] left input is not sorted".to_string()));
+                }
+                Ok(Some((k, v)))
+            }
+        }
+    }
+
+    fn pull_right(&mut self, prev: Option<&K>) -> Result<Option<(K, R)>, Error> {
+        match self.right.next() {
+            None => Ok(None),
+            Some(Err(err)) => Err(err),
+            Some(Ok(v)) => {
+                let k = (self.right_key)(&v);
+                if prev.is_some_and(|p| k < *p) {
+                    return Err(error::value_error("[sort_merge_join] right input is not sorted".to_string()));
+                }
+                Ok(Some((k, v)))
+            }
+        }
+    }
+
+    // Fills `buf` with the rows of the smallest key still pending; returns false when both inputs are done.
+    // The rows already read are in `buf` when an error is returned.
+    fn step(&mut self) -> Result<bool, Error> {
+        if !self.initialized {
+            self.initialized = true;
+            self.left_head = self.pull_left(None)?;
+            self.right_head = self.pull_right(None)?;
+        }
+
+        let ordering = match (self.left_head.as_ref(), self.right_head.as_ref()) {
+            (None, None) => { return Ok(false); },
+            (Some(_), None) => std::cmp::Ordering::Less,
+            (None, Some(_)) => std::cmp::Ordering::Greater,
+            (Some((lk, _)), Some((rk, _))) => lk.cmp(rk)
+        };
+
+        match ordering {
+            std::cmp::Ordering::Less => {
+                let (k, l) = self.left_head.take().unwrap();
+                if self.kind.keeps_left() {
+                    self.buf.push_back((Some(l), None));
+                }
+                self.left_head = self.pull_left(Some(&k))?;
+            },
+            std::cmp::Ordering::Greater => {
+                let (k, r) = self.right_head.take().unwrap();
+                if self.kind.keeps_right() {
+                    self.buf.push_back((None, Some(r)));
+                }
+                self.right_head = self.pull_right(Some(&k))?;
+            },
+            std::cmp::Ordering::Equal => {
+                let (k, l) = self.left_head.take().unwrap();
+                let mut left_run = vec![l];
+                let (_, r) = self.right_head.take().unwrap();
+                let mut right_run = vec![r];
+
+                let mut ret = loop {
+                    match self.pull_left(Some(&k)) {
+                        Ok(Some((lk, l))) if lk == k => { left_run.push(l); },
+                        Ok(head) => {
+                            self.left_head = head;
+                            break Ok(true);
+                        },
+                        Err(err) => { break Err(err); }
+                    }
+                };
+
+                if ret.is_ok() {
+                    ret = loop {
+                        match self.pull_right(Some(&k)) {
+                            Ok(Some((rk, r))) if rk == k => { right_run.push(r); },
+                            Ok(head) => {
+                                self.right_head = head;
+                                break Ok(true);
+                            },
+                            Err(err) => { break Err(err); }
+                        }
+                    };
+                }
+
+                // on an error, the matches of the part of the run already read
+                for l in left_run.iter() {
+                    for r in right_run.iter() {
+                        self.buf.push_back((Some(l.clone()), Some(r.clone())));
+                    }
+                }
+                return ret;
+            }
+        }
+        Ok(true)
+    }
+}
+
+impl<L, R, K> Iterator for SortMergeJoin<L, R, K>
+where
+L: Clone,
+R: Clone,
+K: Ord
+{
+    type Item = Result<(Option<L>, Option<R>),Error>;
+
+    fn next(&mut self) -> Option<Self::Item> {
+        loop {
+            if let Some(row) = self.buf.pop_front() {
+                return Some(Ok(row));
+            }
+
+            if self.iter_finished {
+                return self.error.take().map(Err);
+            }
+
+            match self.step() {
+                Ok(true) => { continue; },
+                Ok(false) => {
+                    self.iter_finished = true;
+                    return None;
+                },
+                Err(err) => {
+                    self.iter_finished = true;
+                    self.error = Some(err);
+                }
+            }
+        }
+    }
+}
+
+/// Joins two inputs sorted ascending by their keys in one streaming pass;
+/// only a run of equal keys is held in memory. Unsorted input is reported as
+/// `Kind::ValueError`, after the rows of the items read before it.
+pub fn sort_merge_join<L, R, K>(left: Box<dyn Iterator<Item = Result<L,Error>>>,
+                                right: Box<dyn Iterator<Item = Result<R,Error>>>,
+                                left_key: fn(&L) -> K,
+                                right_key: fn(&R) -> K,
+                                kind: JoinKind) -> JoinIter<L, R>
+where
+L: Clone + 'static,
+R: Clone + 'static,
+K: Ord + 'static
+{
+    Box::new(SortMergeJoin {
+        left,
+        right,
+        left_key,
+        right_key,
+        kind,
+        left_head: None,
+        right_head: None,
+        initialized: false,
+        buf: VecDeque::new(),
+        error: None,
+        iter_finished: false
+    })
+}
+
+#[cfg(test)]
+mod tests {
+    use crate::utils::{extract_value_from_result_vec, generate_okok_iterator, generate_okokerr_iterator};
+
+    use super::*;
+
+    fn left() -> Box<dyn Iterator<Item = Result<(i32, char),Error>>> {
+        generate_okok_iterator(vec![(1, 'a'), (2, 'b'), (2, 'c'), (4, 'd')])
+    }
+
+    fn right() -> Box<dyn Iterator<Item = Result<(i32, &'static str),Error>>> {
+        generate_okok_iterator(vec![(2, "x"), (3, "y"), (4, "z"), (4, "w")])
+    }
+
+    #[test]
+    fn test_hash_join() {
+        let ret = hash_join(left(), right(), |l| l.0, |r| r.0, JoinKind::Inner);
+        assert_eq!(vec![(Some((2, 'b')), Some((2, "x"))), (Some((2, 'c')), Some((2, "x"))),
+                        (Some((4, 'd')), Some((4, "z"))), (Some((4, 'd')), Some((4, "w")))],
+            extract_value_from_result_vec(ret.collect::<Vec<_>>()).0);
+
+        let ret = hash_join(left(), right(), |l| l.0, |r| r.0, JoinKind::Left);
+        assert_eq!(5, ret.count());
+
+        let ret = hash_join(left(), right(), |l| l.0, |r| r.0, JoinKind::Right);
+        let v = extract_value_from_result_vec(ret.collect::<Vec<_>>()).0;
+        assert_eq!(5, v.len());
+        assert_eq!((None, Some((3, "y"))), v[4]);
+
+        let ret = hash_join(left(), right(), |l| l.0, |r| r.0, JoinKind::FullOuter);
+        let v = extract_value_from_result_vec(ret.collect::<Vec<_>>()).0;
+        assert_eq!(6, v.len());
+        assert_eq!((Some((1, 'a')), None), v[0]);
+        assert_eq!((None, Some((3, "y"))), v[5]);
+    }
+
+    #[test]
+    fn test_sort_merge_join() {
+        let ret = sort_merge_join(left(), right(), |l| l.0, |r| r.0, JoinKind::Inner);
+        assert_eq!(vec![(Some((2, 'b')), Some((2, "x"))), (Some((2, 'c')), Some((2, "x"))),
+                        (Some((4, 'd')), Some((4, "z"))), (Some((4, 'd')), Some((4, "w")))],
+            extract_value_from_result_vec(ret.collect::<Vec<_>>()).0);
+
+        let ret = sort_merge_join(left(), right(), |l| l.0, |r| r.0, JoinKind::FullOuter);
+        assert_eq!(vec![(Some((1, 'a')), None), (Some((2, 'b')), Some((2, "x"))), (Some((2, 'c')), Some((2, "x"))),
+                        (None, Some((3, "y"))), (Some((4, 'd')), Some((4, "z"))), (Some((4, 'd')), Some((4, "w")))],
+            extract_value_from_result_vec(ret.collect::<Vec<_>>()).0);
+
+        let ret = sort_merge_join(left(), right(), |l| l.0, |r| r.0, JoinKind::Left);
+        assert_eq!(5, ret.count());
+
+        let ret = sort_merge_join(left(), right(), |l| l.0, |r| r.0, JoinKind::Right);
+        assert_eq!(5, ret.count());
+    }
+
+    #[test]
+    fn test_error() {
+        let mut ret = sort_merge_join(generate_okok_iterator(vec![2, 1]), generate_okok_iterator(vec![1, 2]), |l| *l, |r| *r, JoinKind::Inner);
+        assert_eq!(Some(Ok((Some(2), Some(2)))), ret.next());
+        assert_eq!(error::Kind::ValueError, ret.next().unwrap().err().unwrap().kind());
+        assert_eq!(None, ret.next());
+
+        // the rows read before an error come first
+        let ret = sort_merge_join(generate_okok_iterator(vec![1, 0]), generate_okok_iterator(vec![5]), |l| *l, |r| *r, JoinKind::Left);
+        let ret = extract_value_from_result_vec(ret.collect::<Vec<_>>());
+        assert_eq!(vec![(Some(1), None)], ret.0);
+        assert_eq!(error::Kind::ValueError, ret.1.unwrap().kind());
+
+        let ret = sort_merge_join(generate_okok_iterator(vec![1, 1, 2]),
+                                  generate_okokerr_iterator(vec![1, 1], error::overflow_error("[test]".to_string())),
+                                  |l| *l, |r| *r, JoinKind::Inner);
+        let ret = extract_value_from_result_vec(ret.collect::<Vec<_>>());
+        assert_eq!(vec![(Some(1), Some(1)); 4], ret.0);
+        assert_eq!(error::Kind::OverflowError, ret.1.unwrap().kind());
+
+        let mut ret = hash_join(generate_okok_iterator(vec![1]),
+                                generate_okokerr_iterator(vec![1], error::overflow_error("[test]".to_string())),
+                                |l| *l, |r| *r, JoinKind::Inner);
+        assert_eq!(error::Kind::OverflowError, ret.next().unwrap().err().unwrap().kind());
+        assert_eq!(None, ret.next());
+    }
+}
@@ -0,0 +1,175 @@
+use std::cmp::Ordering;
+use std::collections::BinaryHeap;
+
+use crate::error::Error;
+
+struct HeapEntry<T, K> {
+    key: K,
+    source: usize,
+    value: T,
+    reverse: bool
+}
+
+impl<T, K: Ord> PartialEq for HeapEntry<T, K> {
+    fn eq(&self, other: &Self) -> bool {
+        self.cmp(other) == Ordering::Equal
+    }
+}
+
+impl<T, K: Ord> Eq for HeapEntry<T, K> {}
+
+impl<T, K: Ord> PartialOrd for HeapEntry<T, K> {
+    fn partial_cmp(&self, other: &Self) -> Option<Ordering> {
+        Some(self.cmp(other))
+    }
+}
+
+impl<T, K: Ord> Ord for HeapEntry<T, K> {
+    // BinaryHeap pops the greatest entry: make it the next key in output
+    // order, and among equal keys the one from the earliest input.
+    fn cmp(&self, other: &Self) -> Ordering {
+        let by_key = if self.reverse {
+            self.key.cmp(&other.key)
+        } else {
+            other.key.cmp(&self.key)
+        };
+        by_key.then_with(|| other.source.cmp(&self.source))
+    }
+}
+
+struct Merge<T, K> {
+    iter_vec: Vec<Box<dyn Iterator<Item = Result<T,Error>>>>,
+    key: fn(&T) -> K,
+    reverse: bool,
+    heap: BinaryHeap<HeapEntry<T, K>>,
+    initialized: bool,
+    pending_error: Option<Error>,
+    iter_finished: bool
+}
+
+impl<T, K> Merge<T, K>
+where K: Ord
+{
+    fn pull(&mut self, source: usize) {
+        match self.iter_vec[source].next() {
+            None => {},
+            Some(Err(err)) => {
+                self.pending_error = Some(err);
+            },
+            Some(Ok(value)) => {
+                self.heap.push(HeapEntry {
+                    key: (self.key)(&value),
+                    source,
+                    value,
+                    reverse: self.reverse
+                });
+            }
+        }
+    }
+}
+
+impl<T, K> Iterator for Merge<T, K>
+where K: Ord
+{
+    type Item = Result<T,Error>;
+
+    fn next(&mut self) -> Option<Self::Item> {
+        if self.iter_finished {
+            return None;
+        }
+
+        if !self.initialized {
+            self.initialized = true;
+            for source in 0..self.iter_vec.len() {
+                self.pull(source);
+                if self.pending_error.is_some() {
+                    break;
+                }
+            }
+        }
+
+        if let Some(err) = self.pending_error.take() {
+            self.iter_finished = true;
+            return Some(Err(err));
+        }
+
+        match self.heap.pop() {
+            None => {
+                self.iter_finished = true;
+                None
+            },
+            Some(entry) => {
+                self.pull(entry.source);
+                Some(Ok(entry.value))
+            }
+        }
+    }
+}
+
+/// https://more-itertools.readthedocs.io/en/v10.2.0/api.html#more_itertools.collate
+///
+/// Merges already sorted inputs into one sorted stream (like Python's
+/// `heapq.merge`); items with equal keys come out in input order.
+pub fn merge<T, K>(iter_vec: Vec<Box<dyn Iterator<Item = Result<T,Error>>>>, key: fn(&T) -> K, reverse: bool) -> Box<dyn Iterator<Item = Result<T,Error>>>
+where
+T: 'static,
+K: Ord + 'static
+{
+    Box::new(Merge {
+        heap: BinaryHeap::with_capacity(iter_vec.len()),
+        iter_vec,
+        key,
+        reverse,
+        initialized: false,
+        pending_error: None,
+        iter_finished: false
+    })
+}
+
+#[cfg(test)]
+mod tests {
+    use crate::error;
+    use crate::utils::{extract_value_from_result_vec, generate_okok_iterator, generate_okokerr_iterator};
+
+    use super::*;
+
+    #[test]
+    fn test1() {
+        let v = vec![generate_okok_iterator(vec![0, 3, 6, 9]),
+                    generate_okok_iterator(vec![1, 4, 7]),
+                    generate_okok_iterator(vec![2, 5, 8])];
+        let ret = merge(v, |x| *x, false);
+        assert_eq!((0..10).collect::<Vec<_>>(), extract_value_from_result_vec(ret.collect::<Vec<_>>()).0);
+
+        let v = vec![generate_okok_iterator(vec![5, 3, 1]),
+                    generate_okok_iterator(Vec::new()),
+                    generate_okok_iterator(vec![4, 2, 0])];
+        let ret = merge(v, |x| *x, true);
+        assert_eq!(vec![5, 4, 3, 2, 1, 0], extract_value_from_result_vec(ret.collect::<Vec<_>>()).0);
+    }
+
+    #[test]
+    fn test2_stable() {
+        let v = vec![generate_okok_iterator(vec![(1, 'a'), (2, 'a')]),
+                    generate_okok_iterator(vec![(1, 'b'), (2, 'b')]),
+                    generate_okok_iterator(vec![(1, 'c')])];
+        let ret = merge(v, |x| x.0, false);
+        assert_eq!(vec![(1, 'a'), (1, 'b'), (1, 'c'), (2, 'a'), (2, 'b')], extract_value_from_result_vec(ret.collect::<Vec<_>>()).0);
+
+        let v = vec![generate_okok_iterator(vec![(2, 'a'), (1, 'a')]),
+                    generate_okok_iterator(vec![(2, 'b'), (1, 'b')])];
+        let ret = merge(v, |x| x.0, true);
+        assert_eq!(vec![(2, 'a'), (2, 'b'), (1, 'a'), (1, 'b')], extract_value_from_result_vec(ret.collect::<Vec<_>>()).0);
+    }
+
+    #[test]
+    fn test3_error() {
+        let v = vec![generate_okok_iterator(vec![1, 3, 5]),
+                    generate_okokerr_iterator(vec![2], error::overflow_error("[test]".to_string()))];
+        let mut ret = merge(v, |x| *x, false);
+        assert_eq!(Some(Ok(1)), ret.next());
+        assert_eq!(Some(Ok(2)), ret.next());
+        assert_eq!(error::Kind::OverflowError, ret.next().unwrap().err().unwrap().kind());
+        assert_eq!(None, ret.next());
+    }
+}
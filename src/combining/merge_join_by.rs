@@ -0,0 +1,120 @@
+use std::cmp::Ordering;
+
+use crate::error::Error;
+
+/// An item of `merge_join_by`: present only on the left, only on the right, or on both sides.
+#[derive(Clone, Debug, PartialEq, Eq)]
+pub enum EitherOrBoth<L, R> {
+    Left(L),
+    Right(R),
+    Both(L, R)
+}
+
+struct MergeJoinBy<L, R> {
+    left: Box<dyn Iterator<Item = Result<L,Error>>>,
+    right: Box<dyn Iterator<Item = Result<R,Error>>>,
+    cmp: fn(&L, &R) -> Ordering,
+    left_peek: Option<L>,
+    right_peek: Option<R>,
+    iter_finished: bool
+}
+
+impl<L, R> Iterator for MergeJoinBy<L, R>
+{
+    type Item = Result<EitherOrBoth<L, R>,Error>;
+
+    fn next(&mut self) -> Option<Self::Item> {
+        if self.iter_finished {
+            return None;
+        }
+
+        if self.left_peek.is_none() {
+            match self.left.next() {
+                None => {},
+                Some(Err(err)) => {
+                    self.iter_finished = true;
+                    return Some(Err(err));
+                },
+                Some(Ok(v)) => { self.left_peek = Some(v); }
+            }
+        }
+        if self.right_peek.is_none() {
+            match self.right.next() {
+                None => {},
+                Some(Err(err)) => {
+                    self.iter_finished = true;
+                    return Some(Err(err));
+                },
+                Some(Ok(v)) => { self.right_peek = Some(v); }
+            }
+        }
+
+        match (self.left_peek.take(), self.right_peek.take()) {
+            (None, None) => {
+                self.iter_finished = true;
+                None
+            },
+            (Some(l), None) => Some(Ok(EitherOrBoth::Left(l))),
+            (None, Some(r)) => Some(Ok(EitherOrBoth::Right(r))),
+            (Some(l), Some(r)) => {
+                match (self.cmp)(&l, &r) {
+                    Ordering::Less => {
+                        self.right_peek = Some(r);
+                        Some(Ok(EitherOrBoth::Left(l)))
+                    },
+                    Ordering::Greater => {
+                        self.left_peek = Some(l);
+                        Some(Ok(EitherOrBoth::Right(r)))
+                    },
+                    Ordering::Equal => Some(Ok(EitherOrBoth::Both(l, r)))
+                }
+            }
+        }
+    }
+}
+
+/// Walks two inputs sorted consistently with `cmp`, pairing up the items that compare equal.
+pub fn merge_join_by<L, R>(left: Box<dyn Iterator<Item = Result<L,Error>>>,
+                           right: Box<dyn Iterator<Item = Result<R,Error>>>,
+                           cmp: fn(&L, &R) -> Ordering) -> Box<dyn Iterator<Item = Result<EitherOrBoth<L, R>,Error>>>
+where
+L: 'static,
+R: 'static
+{
+    Box::new(MergeJoinBy {
+        left,
+        right,
+        cmp,
+        left_peek: None,
+        right_peek: None,
+        iter_finished: false
+    })
+}
+
+#[cfg(test)]
+mod tests {
+    use crate::error;
+    use crate::utils::{extract_value_from_result_vec, generate_okok_iterator, generate_okokerr_iterator};
+
+    use super::*;
+    use super::EitherOrBoth::{Both, Left, Right};
+
+    #[test]
+    fn test1() {
+        let ret = merge_join_by(generate_okok_iterator(vec![1, 2, 4, 6]),
+                                generate_okok_iterator(vec!["2", "3", "4", "7"]),
+                                |l, r| l.cmp(&r.parse::<i32>().unwrap()));
+        assert_eq!(vec![Left(1), Both(2, "2"), Right("3"), Both(4, "4"), Left(6), Right("7")],
+            extract_value_from_result_vec(ret.collect::<Vec<_>>()).0);
+    }
+
+    #[test]
+    fn test2_error() {
+        let mut ret = merge_join_by(generate_okok_iterator(vec![1, 2]),
+                                    generate_okokerr_iterator(vec![1], error::overflow_error("[test]".to_string())),
+                                    |l, r| l.cmp(r));
+        assert_eq!(Some(Ok(Both(1, 1))), ret.next());
+        assert_eq!(error::Kind::OverflowError, ret.next().unwrap().err().unwrap().kind());
+        assert_eq!(None, ret.next());
+    }
+}
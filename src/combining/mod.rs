@@ -12,4 +12,22 @@ pub mod roundrobin;
 
 pub mod prepend;
 
-pub mod dotproduct;
\ No newline at end of file
+pub mod dotproduct;
+
+pub mod merge;
+
+pub mod merge_join_by;
+
+pub mod sorted_set;
+
+pub mod join;
+
+pub mod external_sort;
+
+pub mod zip_tuple;
+
+pub mod zip_broadcast;
+
+pub mod interleave_evenly;
+
+pub mod weighted_roundrobin;
\ No newline at end of file
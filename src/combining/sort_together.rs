@@ -1,21 +1,181 @@
-use crate::utils::argsort;
+use std::cmp::Ordering;
 
-pub fn sort_together2<T1, T2>(v1: &Vec<T1>, v2: &Vec<T2>, reverse: bool) -> (Vec<T1>, Vec<T2>)
-where T1: Ord + Clone,
-T2: Clone
-{
-    let mut sort_result = argsort(v1.as_slice());
+use crate::error::{self, Error};
+use crate::utils::apply_permutation;
+
+/// A group of equally long columns that can be reordered together.
+///
+/// Implemented for tuples of up to 8 `Vec`s of any element types, and for
+/// `Vec<Vec<T>>` when the number of columns is only known at runtime.
+pub trait Columns {
+    /// One row as references into every column, e.g. `(&T0, &T1)`.
+    type Row<'a> where Self: 'a;
+
+    fn column_lens(&self) -> Vec<usize>;
+    fn row(&self, i: usize) -> Self::Row<'_>;
+    fn permute(&mut self, perm: &[usize]);
+}
+
+/// Columns whose elements are all `Ord`, so that any of them can be part of `key_list`.
+pub trait OrdColumns: Columns {
+    fn column_count(&self) -> usize;
+    fn cmp_column(&self, col: usize, i: usize, j: usize) -> Ordering;
+}
+
+macro_rules! impl_columns_for_tuple {
+    ($count:expr; $($idx:tt $t:ident),+) => {
+        impl<$($t),+> Columns for ($(Vec<$t>,)+) {
+            type Row<'a> = ($(&'a $t,)+) where Self: 'a;
+
+            fn column_lens(&self) -> Vec<usize> {
+                vec![$(self.$idx.len()),+]
+            }
+
+            fn row(&self, i: usize) -> Self::Row<'_> {
+                ($(&self.$idx[i],)+)
+            }
+
+            fn permute(&mut self, perm: &[usize]) {
+                $(apply_permutation(&mut self.$idx, perm);)+
+            }
+        }
+
+        impl<$($t: Ord),+> OrdColumns for ($(Vec<$t>,)+) {
+            fn column_count(&self) -> usize {
+                $count
+            }
+
+            fn cmp_column(&self, col: usize, i: usize, j: usize) -> Ordering {
+                match col {
+                    $($idx => self.$idx[i].cmp(&self.$idx[j]),)+
+                    _ => Ordering::Equal
+                }
+            }
+        }
+    };
+}
+
+impl_columns_for_tuple!(1; 0 T0);
+impl_columns_for_tuple!(2; 0 T0, 1 T1);
+impl_columns_for_tuple!(3; 0 T0, 1 T1, 2 T2);
+impl_columns_for_tuple!(4; 0 T0, 1 T1, 2 T2, 3 T3);
+impl_columns_for_tuple!(5; 0 T0, 1 T1, 2 T2, 3 T3, 4 T4);
+impl_columns_for_tuple!(6; 0 T0, 1 T1, 2 T2, 3 T3, 4 T4, 5 T5);
+impl_columns_for_tuple!(7; 0 T0, 1 T1, 2 T2, 3 T3, 4 T4, 5 T5, 6 T6);
+impl_columns_for_tuple!(8; 0 T0, 1 T1, 2 T2, 3 T3, 4 T4, 5 T5, 6 T6, 7 T7);
+
+impl<T> Columns for Vec<Vec<T>> {
+    type Row<'a> = Vec<&'a T> where Self: 'a;
+
+    fn column_lens(&self) -> Vec<usize> {
+        self.iter().map(|col| col.len()).collect()
+    }
+
+    fn row(&self, i: usize) -> Self::Row<'_> {
+        self.iter().map(|col| &col[i]).collect()
+    }
+
+    fn permute(&mut self, perm: &[usize]) {
+        for col in self.iter_mut() {
+            apply_permutation(col, perm);
+        }
+    }
+}
+
+impl<T: Ord> OrdColumns for Vec<Vec<T>> {
+    fn column_count(&self) -> usize {
+        self.len()
+    }
+
+    fn cmp_column(&self, col: usize, i: usize, j: usize) -> Ordering {
+        self[col][i].cmp(&self[col][j])
+    }
+}
+
+fn common_len<C: Columns>(columns: &C) -> Result<usize, Error> {
+    let lens = columns.column_lens();
+    match lens.first() {
+        None => Ok(0),
+        Some(first) => {
+            if lens.iter().any(|len| len != first) {
+                return Err(error::value_error("[sort_together] columns have different lengths".to_string()));
+            }
+            Ok(*first)
+        }
+    }
+}
+
+// Stable sort of the row indices; `reverse` flips the comparison, not the result,
+// so rows with equal keys keep their original order either way.
+fn sorted_permutation(len: usize, cmp: impl Fn(usize, usize) -> Ordering, reverse: bool) -> Vec<usize> {
+    let mut perm = (0..len).collect::<Vec<_>>();
     if reverse {
-        sort_result.reverse();
+        perm.sort_by(|i, j| cmp(*j, *i));
+    } else {
+        perm.sort_by(|i, j| cmp(*i, *j));
     }
-    let mut ret1 = Vec::<T1>::with_capacity(v1.len());
-    let mut ret2 = Vec::<T2>::with_capacity(v1.len());
-    for old_index in sort_result.into_iter() {
-        ret1.push(v1.get(old_index).unwrap().clone());
-        ret2.push(v2.get(old_index).unwrap().clone());
+    perm
+}
+
+/// https://more-itertools.readthedocs.io/en/v10.2.0/api.html#more_itertools.sort_together
+///
+/// Sorts all columns by the columns listed in `key_list`, compared in that order.
+pub fn sort_together_in_place<C>(columns: &mut C, key_list: &[usize], reverse: bool) -> Result<(), Error>
+where C: OrdColumns
+{
+    if key_list.is_empty() {
+        return Err(error::value_error("[sort_together] key_list should not be empty".to_string()));
     }
+    if let Some(col) = key_list.iter().find(|col| **col >= columns.column_count()) {
+        return Err(error::value_error(format!("[sort_together] key column {} is out of range", col)));
+    }
+
+    let len = common_len(columns)?;
+    let perm = sorted_permutation(len, |i, j| {
+        key_list.iter().fold(Ordering::Equal, |acc, col| acc.then_with(|| columns.cmp_column(*col, i, j)))
+    }, reverse);
+    columns.permute(&perm);
+    Ok(())
+}
+
+pub fn sort_together<C>(mut columns: C, key_list: &[usize], reverse: bool) -> Result<C, Error>
+where C: OrdColumns
+{
+    sort_together_in_place(&mut columns, key_list, reverse)?;
+    Ok(columns)
+}
+
+/// Sorts all columns by `key` applied to each row, e.g. `|(a, _, c)| (*c, *a)`
+/// to sort by the third then the first column.
+pub fn sort_together_by_key_in_place<C, K>(columns: &mut C, key: fn(C::Row<'_>) -> K, reverse: bool) -> Result<(), Error>
+where
+C: Columns,
+K: Ord
+{
+    let len = common_len(columns)?;
+    let keys = (0..len).map(|i| key(columns.row(i))).collect::<Vec<_>>();
+    let perm = sorted_permutation(len, |i, j| keys[i].cmp(&keys[j]), reverse);
+    columns.permute(&perm);
+    Ok(())
+}
+
+pub fn sort_together_by_key<C, K>(mut columns: C, key: fn(C::Row<'_>) -> K, reverse: bool) -> Result<C, Error>
+where
+C: Columns,
+K: Ord
+{
+    sort_together_by_key_in_place(&mut columns, key, reverse)?;
+    Ok(columns)
+}
 
-    return (ret1, ret2);
+pub fn sort_together2<T1, T2>(v1: &Vec<T1>, v2: &Vec<T2>, reverse: bool) -> (Vec<T1>, Vec<T2>)
+where T1: Ord + Clone,
+T2: Clone
+{
+    let mut columns = (v1.clone(), v2.clone());
+    let perm = sorted_permutation(v1.len(), |i, j| v1[i].cmp(&v1[j]), reverse);
+    columns.permute(&perm);
+    columns
 }
 
 pub fn sort_together3<T1, T2, T3>(v1: &Vec<T1>, v2: &Vec<T2>, v3: &Vec<T3>, reverse: bool) -> (Vec<T1>, Vec<T2>, Vec<T3>)
@@ -23,20 +183,10 @@ where T1: Ord + Clone,
 T2: Clone,
 T3: Clone
 {
-    let mut sort_result = argsort(v1.as_slice());
-    if reverse {
-        sort_result.reverse();
-    }
-    let mut ret1 = Vec::<T1>::with_capacity(v1.len());
-    let mut ret2 = Vec::<T2>::with_capacity(v1.len());
-    let mut ret3 = Vec::<T3>::with_capacity(v1.len());
-    for old_index in sort_result.into_iter() {
-        ret1.push(v1.get(old_index).unwrap().clone());
-        ret2.push(v2.get(old_index).unwrap().clone());
-        ret3.push(v3.get(old_index).unwrap().clone());
-    }
-
-    return (ret1, ret2, ret3);
+    let mut columns = (v1.clone(), v2.clone(), v3.clone());
+    let perm = sorted_permutation(v1.len(), |i, j| v1[i].cmp(&v1[j]), reverse);
+    columns.permute(&perm);
+    columns
 }
 
 #[cfg(test)]
@@ -54,7 +204,7 @@ mod tests {
 
     #[test]
     fn test2() {
-        let ret = sort_together3(&vec![4, 3, 2, 1], &vec!['a', 'b', 'c', 'd'], 
+        let ret = sort_together3(&vec![4, 3, 2, 1], &vec!['a', 'b', 'c', 'd'],
                                                 &vec![5, 6, 7, 8], false);
         assert_eq!((vec![1, 2, 3, 4], vec!['d', 'c', 'b', 'a'], vec![8, 7, 6, 5]), ret);
 
@@ -62,4 +212,54 @@ mod tests {
                                                                 &vec![8, 7, 6, 5], true);
         assert_eq!((vec![4, 3, 2, 1], vec!['a', 'b', 'c', 'd'], vec![8, 7, 6, 5]), ret);
     }
-}
\ No newline at end of file
+
+    #[test]
+    fn test3_key_list() {
+        let columns = (vec![1, 2, 1, 2], vec!['d', 'c', 'b', 'a'], vec![3, 3, 3, 3]);
+        let ret = sort_together(columns, &[0, 1], false).ok().unwrap();
+        assert_eq!((vec![1, 1, 2, 2], vec!['b', 'd', 'a', 'c'], vec![3, 3, 3, 3]), ret);
+
+        let columns = (vec![1, 2, 1, 2], vec!['d', 'c', 'b', 'a']);
+        let ret = sort_together(columns, &[1], true).ok().unwrap();
+        assert_eq!((vec![1, 2, 1, 2], vec!['d', 'c', 'b', 'a']), ret);
+    }
+
+    #[test]
+    fn test4_stable() {
+        let columns = (vec![2, 1, 2, 1], vec!["w", "x", "y", "z"]);
+        let ret = sort_together(columns, &[0], false).ok().unwrap();
+        assert_eq!((vec![1, 1, 2, 2], vec!["x", "z", "w", "y"]), ret);
+
+        let columns = (vec![2, 1, 2, 1], vec!["w", "x", "y", "z"]);
+        let ret = sort_together(columns, &[0], true).ok().unwrap();
+        assert_eq!((vec![2, 2, 1, 1], vec!["w", "y", "x", "z"]), ret);
+    }
+
+    #[test]
+    fn test5_by_key() {
+        let columns = (vec![1.5, 0.5, 2.5], vec!["b".to_string(), "c".to_string(), "a".to_string()]);
+        let ret = sort_together_by_key(columns, |(_, s)| s.clone(), false).ok().unwrap();
+        assert_eq!((vec![2.5, 1.5, 0.5], vec!["a".to_string(), "b".to_string(), "c".to_string()]), ret);
+
+        let mut columns = (vec![-3i32, 1, -2], vec!['a', 'b', 'c']);
+        sort_together_by_key_in_place(&mut columns, |(x, _)| x.abs(), true).ok().unwrap();
+        assert_eq!((vec![-3, -2, 1], vec!['a', 'c', 'b']), columns);
+    }
+
+    #[test]
+    fn test6_vec_of_vec() {
+        let mut columns = vec![vec![3, 1, 2], vec![30, 10, 20], vec![0, 0, 0]];
+        sort_together_in_place(&mut columns, &[0], false).ok().unwrap();
+        assert_eq!(vec![vec![1, 2, 3], vec![10, 20, 30], vec![0, 0, 0]], columns);
+    }
+
+    #[test]
+    fn test7_error() {
+        let columns = (vec![1, 2], vec![1]);
+        assert_eq!(error::Kind::ValueError, sort_together(columns, &[0], false).err().unwrap().kind());
+
+        let columns = (vec![1, 2], vec![1, 2]);
+        assert_eq!(error::Kind::ValueError, sort_together(columns.clone(), &[2], false).err().unwrap().kind());
+        assert_eq!(error::Kind::ValueError, sort_together(columns, &[], false).err().unwrap().kind());
+    }
+}
@@ -0,0 +1,263 @@
+use crate::error::{self, Error};
+
+#[derive(Clone, Copy, Debug, PartialEq)]
+enum SetOp {
+    Union,
+    Intersection,
+    Difference,
+    SymmetricDifference
+}
+
+impl SetOp {
+    fn name(&self) -> &'static str {
+        match self {
+            SetOp::Union => "sorted_union",
+            SetOp::Intersection => "sorted_intersection",
+            SetOp::Difference => "sorted_difference",
+            SetOp::SymmetricDifference => "sorted_symmetric_difference"
+        }
+    }
+
+    // Whether a key found in the inputs flagged by `present` belongs to the result.
+    fn keeps(&self, present: &[bool]) -> bool {
+        let count = present.iter().filter(|x| **x).count();
+        match self {
+            SetOp::Union => count > 0,
+            SetOp::Intersection => count == present.len(),
+            SetOp::Difference => present[0] && count == 1,
+            SetOp::SymmetricDifference => count % 2 == 1
+        }
+    }
+
+    // Whether no further key can belong to the result, `None` marking the exhausted inputs.
+    fn ended<K, T>(&self, heads: &[Option<(K, T)>]) -> bool {
+        match self {
+            SetOp::Intersection => heads.iter().any(Option::is_none),
+            SetOp::Difference => heads.first().map_or(true, Option::is_none),
+            SetOp::Union | SetOp::SymmetricDifference => heads.iter().all(Option::is_none)
+        }
+    }
+}
+
+struct SortedSet<T, K> {
+    iter_vec: Vec<Box<dyn Iterator<Item = Result<T,Error>>>>,
+    key: fn(&T) -> K,
+    op: SetOp,
+    // next (key, item) of every input, None once it is exhausted
+    heads: Vec<Option<(K, T)>>,
+    initialized: bool,
+    iter_finished: bool
+}
+
+impl<T, K> SortedSet<T, K>
+where K: Ord
+{
+    // Reads the next item of input `i`, checking that it does not go below `prev`.
+    fn pull(&mut self, i: usize, prev: Option<&K>) -> Result<Option<(K, T)>, Error> {
+        match self.iter_vec[i].next() {
+            None => Ok(None),
+            Some(Err(err)) => Err(err),
+            Some(Ok(v)) => {
+                let k = (self.key)(&v);
+                if prev.is_some_and(|p| k < *p) {
+                    return Err(error::value_error(format!("[{}] input {} is not sorted", self.op.name(), i)));
+                }
+                Ok(Some((k, v)))
+            }
+        }
+    }
+
+    fn step(&mut self) -> Result<Option<T>, Error> {
+        if !self.initialized {
+            self.initialized = true;
+            for i in 0..self.iter_vec.len() {
+                let head = self.pull(i, None)?;
+                self.heads.push(head);
+            }
+        }
+
+        loop {
+            if self.op.ended(&self.heads) {
+                return Ok(None);
+            }
+
+            let min_index = self.heads.iter().enumerate()
+                .filter_map(|(i, head)| head.as_ref().map(|(k, _)| (i, k)))
+                .min_by(|a, b| a.1.cmp(b.1).then_with(|| a.0.cmp(&b.0)))
+                .map(|(i, _)| i);
+
+            // some input holds a key since the inputs have not all ended
+            let min_index = min_index.unwrap();
+
+            let (min_key, value) = self.heads[min_index].take().unwrap();
+            let mut present = vec![false; self.heads.len()];
+
+            for (i, is_present) in present.iter_mut().enumerate() {
+                let holds_min = i == min_index || self.heads[i].as_ref().is_some_and(|(k, _)| *k == min_key);
+                if !holds_min {
+                    continue;
+                }
+
+                *is_present = true;
+                // skip the duplicates of min_key in this input
+                self.heads[i] = loop {
+                    match self.pull(i, Some(&min_key))? {
+                        Some((k, _)) if k == min_key => { continue; },
+                        head => { break head; }
+                    }
+                };
+            }
+
+            if self.op.keeps(&present) {
+                return Ok(Some(value));
+            }
+        }
+    }
+}
+
+impl<T, K> Iterator for SortedSet<T, K>
+where K: Ord
+{
+    type Item = Result<T,Error>;
+
+    fn next(&mut self) -> Option<Self::Item> {
+        if self.iter_finished {
+            return None;
+        }
+
+        match self.step() {
+            Ok(Some(v)) => Some(Ok(v)),
+            Ok(None) => {
+                self.iter_finished = true;
+                None
+            },
+            Err(err) => {
+                self.iter_finished = true;
+                Some(Err(err))
+            }
+        }
+    }
+}
+
+fn sorted_set<T, K>(iter_vec: Vec<Box<dyn Iterator<Item = Result<T,Error>>>>, key: fn(&T) -> K, op: SetOp) -> Box<dyn Iterator<Item = Result<T,Error>>>
+where
+T: 'static,
+K: Ord + 'static
+{
+    Box::new(SortedSet {
+        heads: Vec::with_capacity(iter_vec.len()),
+        iter_vec,
+        key,
+        op,
+        initialized: false,
+        iter_finished: false
+    })
+}
+
+/// Distinct keys found in any input. All the functions of this module expect
+/// every input sorted ascending by `key`, yield one item per key (taken from
+/// the first input holding it) and fail with `Kind::ValueError` on unsorted input.
+pub fn sorted_union<T, K>(iter_vec: Vec<Box<dyn Iterator<Item = Result<T,Error>>>>, key: fn(&T) -> K) -> Box<dyn Iterator<Item = Result<T,Error>>>
+where
+T: 'static,
+K: Ord + 'static
+{
+    sorted_set(iter_vec, key, SetOp::Union)
+}
+
+/// Keys found in every input.
+pub fn sorted_intersection<T, K>(iter_vec: Vec<Box<dyn Iterator<Item = Result<T,Error>>>>, key: fn(&T) -> K) -> Box<dyn Iterator<Item = Result<T,Error>>>
+where
+T: 'static,
+K: Ord + 'static
+{
+    sorted_set(iter_vec, key, SetOp::Intersection)
+}
+
+/// Keys of the first input found in none of the others.
+pub fn sorted_difference<T, K>(iter_vec: Vec<Box<dyn Iterator<Item = Result<T,Error>>>>, key: fn(&T) -> K) -> Box<dyn Iterator<Item = Result<T,Error>>>
+where
+T: 'static,
+K: Ord + 'static
+{
+    sorted_set(iter_vec, key, SetOp::Difference)
+}
+
+/// Keys found in an odd number of inputs, i.e. `a ^ b ^ c ...`.
+pub fn sorted_symmetric_difference<T, K>(iter_vec: Vec<Box<dyn Iterator<Item = Result<T,Error>>>>, key: fn(&T) -> K) -> Box<dyn Iterator<Item = Result<T,Error>>>
+where
+T: 'static,
+K: Ord + 'static
+{
+    sorted_set(iter_vec, key, SetOp::SymmetricDifference)
+}
+
+#[cfg(test)]
+mod tests {
+    use crate::utils::{extract_value_from_result_vec, generate_okok_iterator, generate_okokerr_iterator};
+
+    use super::*;
+
+    fn inputs() -> Vec<Box<dyn Iterator<Item = Result<i32,Error>>>> {
+        vec![generate_okok_iterator(vec![1, 2, 2, 4, 5, 7]),
+            generate_okok_iterator(vec![2, 3, 4, 7]),
+            generate_okok_iterator(vec![0, 2, 4, 5])]
+    }
+
+    #[test]
+    fn test1() {
+        let ret = sorted_union(inputs(), |x| *x);
+        assert_eq!(vec![0, 1, 2, 3, 4, 5, 7], extract_value_from_result_vec(ret.collect::<Vec<_>>()).0);
+
+        let ret = sorted_intersection(inputs(), |x| *x);
+        assert_eq!(vec![2, 4], extract_value_from_result_vec(ret.collect::<Vec<_>>()).0);
+
+        let ret = sorted_difference(inputs(), |x| *x);
+        assert_eq!(vec![1], extract_value_from_result_vec(ret.collect::<Vec<_>>()).0);
+
+        let ret = sorted_symmetric_difference(inputs(), |x| *x);
+        assert_eq!(vec![0, 1, 2, 3, 4], extract_value_from_result_vec(ret.collect::<Vec<_>>()).0);
+    }
+
+    #[test]
+    fn test2_key() {
+        let v = vec![generate_okok_iterator(vec![(1, 'a'), (3, 'a')]),
+                    generate_okok_iterator(vec![(1, 'b'), (2, 'b')])];
+        let ret = sorted_union(v, |x| x.0);
+        assert_eq!(vec![(1, 'a'), (2, 'b'), (3, 'a')], extract_value_from_result_vec(ret.collect::<Vec<_>>()).0);
+    }
+
+    #[test]
+    fn test3_unsorted() {
+        let v = vec![generate_okok_iterator(vec![1, 3, 2]),
+                    generate_okok_iterator(vec![1, 2, 3])];
+        let mut ret = sorted_union(v, |x| *x);
+        assert_eq!(Some(Ok(1)), ret.next());
+        assert_eq!(Some(Ok(2)), ret.next());
+        let err = ret.next().unwrap().err().unwrap();
+        assert_eq!(error::Kind::ValueError, err.kind());
+        assert_eq!("[sorted_union] input 0 is not sorted", err.message().unwrap());
+        assert_eq!(None, ret.next());
+    }
+
+    #[test]
+    fn test4_error() {
+        let v = vec![generate_okok_iterator(vec![1, 2, 3]),
+                    generate_okokerr_iterator(vec![1, 2], error::overflow_error("[test]".to_string()))];
+        let mut ret = sorted_intersection(v, |x| *x);
+        assert_eq!(Some(Ok(1)), ret.next());
+        assert_eq!(error::Kind::OverflowError, ret.next().unwrap().err().unwrap().kind());
+        assert_eq!(None, ret.next());
+    }
+
+    #[test]
+    fn test5_stops_early() {
+        let v = vec![generate_okok_iterator(vec![1, 2]), Box::new((1..).map(Ok)) as Box<dyn Iterator<Item = Result<i32,Error>>>];
+        let ret = sorted_intersection(v, |x| *x);
+        assert_eq!(vec![1, 2], extract_value_from_result_vec(ret.collect::<Vec<_>>()).0);
+
+        let v = vec![generate_okok_iterator(vec![1, 3]), Box::new((2..).map(Ok)) as Box<dyn Iterator<Item = Result<i32,Error>>>];
+        let ret = sorted_difference(v, |x| *x);
+        assert_eq!(vec![1], extract_value_from_result_vec(ret.collect::<Vec<_>>()).0);
+    }
+}
@@ -0,0 +1,132 @@
+use crate::error::{self, Error};
+
+struct WeightedRoundrobin<T> {
+    iter_vec: Vec<Box<dyn Iterator<Item = Result<T,Error>>>>,
+    // set to 0 once an input is exhausted
+    weights: Vec<usize>,
+    current: Vec<i128>,
+    initialized: bool,
+    iter_finished: bool
+}
+
+impl<T> WeightedRoundrobin<T> {
+    // Smooth weighted round-robin: the input with the largest credit is picked
+    // and pays back the total weight, so picks are spread out within a cycle.
+    fn pick(&mut self) -> Option<usize> {
+        let total = self.weights.iter().map(|w| *w as i128).sum::<i128>();
+        if total == 0 {
+            return None;
+        }
+
+        let mut best = None;
+        for (i, w) in self.weights.iter().enumerate() {
+            if *w == 0 {
+                continue;
+            }
+            self.current[i] += *w as i128;
+            if best.map_or(true, |b: usize| self.current[i] > self.current[b]) {
+                best = Some(i);
+            }
+        }
+
+        let best = best.unwrap();
+        self.current[best] -= total;
+        Some(best)
+    }
+}
+
+impl<T> Iterator for WeightedRoundrobin<T> {
+    type Item = Result<T,Error>;
+
+    fn next(&mut self) -> Option<Self::Item> {
+        if self.iter_finished {
+            return None;
+        }
+
+        if !self.initialized {
+            self.initialized = true;
+            if self.weights.len() != self.iter_vec.len() {
+                self.iter_finished = true;
+                return Some(Err(error::value_error("[weighted_roundrobin] must have one weight per input".to_string())));
+            }
+        }
+
+        loop {
+            let i = match self.pick() {
+                None => {
+                    self.iter_finished = true;
+                    return None;
+                },
+                Some(i) => i
+            };
+
+            match self.iter_vec[i].next() {
+                Some(Ok(v)) => { return Some(Ok(v)); },
+                Some(Err(err)) => {
+                    self.iter_finished = true;
+                    return Some(Err(err));
+                },
+                None => {
+                    self.weights[i] = 0;
+                    self.current[i] = 0;
+                }
+            }
+        }
+    }
+}
+
+/// Like `roundrobin`, but each input gets a share of the output proportional
+/// to its weight, e.g. weights `[2, 1]` take two items from the first input
+/// for each one from the second. Picks are spread out (`a b a`, not `a a b`);
+/// exhausted inputs are skipped and inputs of weight 0 are never read.
+pub fn weighted_roundrobin<T>(iter_vec: Vec<Box<dyn Iterator<Item = Result<T,Error>>>>, weights: Vec<usize>) -> Box<dyn Iterator<Item = Result<T,Error>>>
+where T: 'static
+{
+    Box::new(WeightedRoundrobin {
+        current: vec![0; weights.len()],
+        iter_vec,
+        weights,
+        initialized: false,
+        iter_finished: false
+    })
+}
+
+#[cfg(test)]
+mod tests {
+    use crate::utils::{extract_value_from_result_vec, generate_okok_iterator, generate_okokerr_iterator};
+
+    use super::*;
+
+    #[test]
+    fn test1() {
+        let v = vec![generate_okok_iterator("AAAAAAAA".chars().collect::<Vec<_>>()),
+                    generate_okok_iterator("BBBB".chars().collect::<Vec<_>>()),
+                    generate_okok_iterator("CCCC".chars().collect::<Vec<_>>())];
+        let ret = weighted_roundrobin(v, vec![5, 1, 1]);
+        assert_eq!("AABACAAAABACBCBC".chars().collect::<Vec<_>>(), extract_value_from_result_vec(ret.collect::<Vec<_>>()).0);
+    }
+
+    #[test]
+    fn test2_equal_weights() {
+        let v = vec![generate_okok_iterator("ABC".chars().collect::<Vec<_>>()),
+                    generate_okok_iterator("D".chars().collect::<Vec<_>>()),
+                    generate_okok_iterator("EF".chars().collect::<Vec<_>>()),
+                    generate_okok_iterator("XYZ".chars().collect::<Vec<_>>())];
+        let ret = weighted_roundrobin(v, vec![1, 1, 1, 0]);
+        assert_eq!(vec!['A', 'D', 'E', 'B', 'F', 'C'], extract_value_from_result_vec(ret.collect::<Vec<_>>()).0);
+    }
+
+    #[test]
+    fn test3_error() {
+        let mut ret = weighted_roundrobin(vec![generate_okok_iterator(vec![1])], vec![]);
+        assert_eq!(error::Kind::ValueError, ret.next().unwrap().err().unwrap().kind());
+        assert_eq!(None, ret.next());
+
+        let v = vec![generate_okokerr_iterator(vec![1], error::overflow_error("[test]".to_string())), generate_okok_iterator(vec![2, 3])];
+        let mut ret = weighted_roundrobin(v, vec![1, 1]);
+        assert_eq!(Some(Ok(1)), ret.next());
+        assert_eq!(Some(Ok(2)), ret.next());
+        assert_eq!(error::Kind::OverflowError, ret.next().unwrap().err().unwrap().kind());
+        assert_eq!(None, ret.next());
+    }
+}
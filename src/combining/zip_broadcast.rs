@@ -0,0 +1,136 @@
+use crate::combining::zip_tuple::{zip_tuple_bounded, ZipSources};
+use crate::error::Error;
+
+/// One argument of `zip_broadcast`: a value repeated alongside the other
+/// inputs, or an iterator.
+pub enum Broadcast<T> {
+    Scalar(T),
+    Iter(Box<dyn Iterator<Item = Result<T,Error>>>)
+}
+
+impl<T> From<Box<dyn Iterator<Item = Result<T,Error>>>> for Broadcast<T> {
+    fn from(iter: Box<dyn Iterator<Item = Result<T,Error>>>) -> Self {
+        Broadcast::Iter(iter)
+    }
+}
+
+impl<T> Broadcast<T>
+where T: Clone + 'static
+{
+    // Turns the argument into an input of the zip, counting the iterators in `finite`.
+    fn into_source(self, finite: &mut usize) -> Box<dyn Iterator<Item = Result<T,Error>>> {
+        match self {
+            Broadcast::Scalar(v) => Box::new(std::iter::repeat(v).map(Ok)),
+            Broadcast::Iter(iter) => {
+                *finite += 1;
+                iter
+            }
+        }
+    }
+}
+
+/// A tuple of up to 12 `Broadcast` arguments.
+pub trait BroadcastSources {
+    type Sources: ZipSources;
+
+    /// The zip inputs and how many of them are iterators.
+    fn into_sources(self) -> (Self::Sources, usize);
+}
+
+macro_rules! impl_broadcast_sources_for_tuple {
+    ($($idx:tt $t:ident),+) => {
+        impl<$($t: Clone + 'static),+> BroadcastSources for ($(Broadcast<$t>,)+) {
+            type Sources = ($(Box<dyn Iterator<Item = Result<$t,Error>>>,)+);
+
+            fn into_sources(self) -> (Self::Sources, usize) {
+                let mut finite = 0;
+                let sources = ($(self.$idx.into_source(&mut finite),)+);
+                (sources, finite)
+            }
+        }
+    };
+}
+
+impl_broadcast_sources_for_tuple!(0 T0);
+impl_broadcast_sources_for_tuple!(0 T0, 1 T1);
+impl_broadcast_sources_for_tuple!(0 T0, 1 T1, 2 T2);
+impl_broadcast_sources_for_tuple!(0 T0, 1 T1, 2 T2, 3 T3);
+impl_broadcast_sources_for_tuple!(0 T0, 1 T1, 2 T2, 3 T3, 4 T4);
+impl_broadcast_sources_for_tuple!(0 T0, 1 T1, 2 T2, 3 T3, 4 T4, 5 T5);
+impl_broadcast_sources_for_tuple!(0 T0, 1 T1, 2 T2, 3 T3, 4 T4, 5 T5, 6 T6);
+impl_broadcast_sources_for_tuple!(0 T0, 1 T1, 2 T2, 3 T3, 4 T4, 5 T5, 6 T6, 7 T7);
+impl_broadcast_sources_for_tuple!(0 T0, 1 T1, 2 T2, 3 T3, 4 T4, 5 T5, 6 T6, 7 T7, 8 T8);
+impl_broadcast_sources_for_tuple!(0 T0, 1 T1, 2 T2, 3 T3, 4 T4, 5 T5, 6 T6, 7 T7, 8 T8, 9 T9);
+impl_broadcast_sources_for_tuple!(0 T0, 1 T1, 2 T2, 3 T3, 4 T4, 5 T5, 6 T6, 7 T7, 8 T8, 9 T9, 10 T10);
+impl_broadcast_sources_for_tuple!(0 T0, 1 T1, 2 T2, 3 T3, 4 T4, 5 T5, 6 T6, 7 T7, 8 T8, 9 T9, 10 T10, 11 T11);
+
+/// https://more-itertools.readthedocs.io/en/v10.2.0/api.html#more_itertools.zip_broadcast
+///
+/// Zips a tuple of `Broadcast` arguments, repeating the scalars alongside the
+/// iterators. With only scalars, yields a single tuple. With `strict`, inputs
+/// of different lengths fail like `zip_equal`.
+pub fn zip_broadcast<B>(args: B, strict: bool) -> Box<dyn Iterator<Item = Result<<B::Sources as ZipSources>::Item,Error>>>
+where
+B: BroadcastSources,
+B::Sources: 'static,
+<B::Sources as ZipSources>::Item: 'static
+{
+    let (sources, finite) = args.into_sources();
+    let ret = zip_tuple_bounded(sources, strict, finite);
+    if finite == 0 {
+        return Box::new(ret.take(1));
+    }
+    ret
+}
+
+#[cfg(test)]
+mod tests {
+    use crate::error;
+    use crate::utils::{extract_value_from_result_vec, generate_okok_iterator, generate_okokerr_iterator};
+
+    use super::*;
+
+    #[test]
+    fn test1() {
+        let ret = zip_broadcast((Broadcast::Scalar('a'),
+                                 Broadcast::Iter(generate_okok_iterator(vec![1, 2, 3])),
+                                 Broadcast::Iter(generate_okok_iterator(vec![0.5, 1.5, 2.5]))), false);
+        assert_eq!(vec![('a', 1, 0.5), ('a', 2, 1.5), ('a', 3, 2.5)], extract_value_from_result_vec(ret.collect::<Vec<_>>()).0);
+
+        let ret = zip_broadcast((generate_okok_iterator(vec![1, 2, 3]).into(), Broadcast::Scalar("x")), false);
+        assert_eq!(vec![(1, "x"), (2, "x"), (3, "x")], extract_value_from_result_vec(ret.collect::<Vec<_>>()).0);
+    }
+
+    #[test]
+    fn test2_scalars_only() {
+        let ret = zip_broadcast((Broadcast::Scalar(1), Broadcast::Scalar('b')), true);
+        assert_eq!(vec![(1, 'b')], extract_value_from_result_vec(ret.collect::<Vec<_>>()).0);
+    }
+
+    #[test]
+    fn test3_strict() {
+        let ret = zip_broadcast((Broadcast::Scalar(0),
+                                 Broadcast::Iter(generate_okok_iterator(vec![1, 2, 3])),
+                                 Broadcast::Iter(generate_okok_iterator(vec![4, 5]))), false);
+        assert_eq!(vec![(0, 1, 4), (0, 2, 5)], extract_value_from_result_vec(ret.collect::<Vec<_>>()).0);
+
+        let mut ret = zip_broadcast((Broadcast::Scalar(0),
+                                     Broadcast::Iter(generate_okok_iterator(vec![1, 2, 3])),
+                                     Broadcast::Iter(generate_okok_iterator(vec![4, 5]))), true);
+        assert_eq!(Some(Ok((0, 1, 4))), ret.next());
+        assert_eq!(Some(Ok((0, 2, 5))), ret.next());
+        let err = ret.next().unwrap().err().unwrap();
+        assert_eq!(error::Kind::IteratorError, err.kind());
+        assert_eq!("Iterables have different lengths: input 2 ended early", err.message().unwrap());
+        assert_eq!(None, ret.next());
+    }
+
+    #[test]
+    fn test4_error() {
+        let mut ret = zip_broadcast((Broadcast::Scalar(0),
+                                     Broadcast::Iter(generate_okokerr_iterator(vec![1], error::overflow_error("[test]".to_string())))), false);
+        assert_eq!(Some(Ok((0, 1))), ret.next());
+        assert_eq!(error::Kind::OverflowError, ret.next().unwrap().err().unwrap().kind());
+        assert_eq!(None, ret.next());
+    }
+}
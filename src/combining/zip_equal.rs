@@ -44,6 +44,8 @@ T: Clone + 'static
                 }
             }
 
+            let ended = self.buf2.iter().enumerate().filter(|(_, x)| x.is_none()).map(|(i, _)| i).collect::<Vec<_>>();
+
             if self.buf2.iter().all(|x| match x {
                 None => { return true; },
                 Some(_) => { return false; }
@@ -72,7 +74,7 @@ T: Clone + 'static
                 }
                 if self.buf.len() != self.iter_vec.len() {
                     self.iter_finished = true;
-                    return Some(Err(error::any_error(error::Kind::IteratorError, "Iterables have different lengths".to_string())));
+                    return Some(Err(length_mismatch_error(&ended)));
                 } else {
                     let mut ret = Vec::new();
                     while self.buf.len() > 0 {
@@ -85,6 +87,14 @@ T: Clone + 'static
     }
 }
 
+/// The error of the strict zips when some inputs run out before the others;
+/// `ended` lists the positions of the inputs that ran out first.
+pub(crate) fn length_mismatch_error(ended: &[usize]) -> Error {
+    let inputs = ended.iter().map(|i| i.to_string()).collect::<Vec<_>>().join(", ");
+    let which = if ended.len() == 1 { "input" } else { "inputs" };
+    error::any_error(error::Kind::IteratorError, format!("Iterables have different lengths: {} {} ended early", which, inputs))
+}
+
 pub fn zip_equal<T>(iter_vec: Vec<Box<dyn Iterator<Item = Result<T,Error>>>>) -> Box<dyn Iterator<Item = Result<Vec<T>,Error>>> 
 where T: Clone + 'static
 {
@@ -129,7 +139,9 @@ mod tests {
 
         assert_eq!(Some(Ok(vec![1, 4, 6])), iter.next());
         assert_eq!(Some(Ok(vec![2, 5, 7])), iter.next());
-        assert_eq!(error::Kind::IteratorError, iter.next().unwrap().err().unwrap().kind());
+        let err = iter.next().unwrap().err().unwrap();
+        assert_eq!(error::Kind::IteratorError, err.kind());
+        assert_eq!("Iterables have different lengths: input 1 ended early", err.message().unwrap());
         assert_eq!(None, iter.next());
     }
 
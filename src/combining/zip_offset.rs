@@ -1,7 +1,5 @@
 use std::collections::VecDeque;
-use crate::error::Error;
-
-use crate::selecting::take::take;
+use crate::error::{self, Error};
 
 
 pub struct ZipOffset<T> {
@@ -10,7 +8,8 @@ pub struct ZipOffset<T> {
     iter_vec: Vec<Box<dyn Iterator<Item = Result<T,Error>>>>,
     iter_finished: bool,
     longest: bool,
-    fillvalue: T
+    fillvalues: Vec<T>,
+    init_error: Option<Error>
 }
 
 impl<T> Iterator for ZipOffset<T> 
@@ -20,6 +19,11 @@ T: Clone + 'static
     type Item = Result<Vec<T>,Error>;
 
     fn next(&mut self) -> Option<Self::Item> {
+        if let Some(err) = self.init_error.take() {
+            self.iter_finished = true;
+            return Some(Err(err));
+        }
+
         loop {
             if self.iter_finished {
                 return None;
@@ -59,18 +63,10 @@ T: Clone + 'static
                 }) {
                     self.iter_finished = true;
                 } else {
-                    while self.buf2.len() > 0 {
-                        match self.buf2.pop_front() {
-                            None => {
-                                self.buf.push_back(self.fillvalue.clone());
-                            },
-                            Some(v) => {
-                                if !v.is_none() {
-                                    self.buf.push_back(v.unwrap());
-                                } else { 
-                                    self.buf.push_back(self.fillvalue.clone());
-                                }
-                            }
+                    for (index, v) in self.buf2.drain(..).enumerate() {
+                        match v {
+                            Some(v) => { self.buf.push_back(v); },
+                            None => { self.buf.push_back(self.fillvalues[index].clone()); }
                         }
                     }
                 }
@@ -90,24 +86,65 @@ T: Clone + 'static
     }
 }
 
-pub fn zip_offset<T>(mut iter_vec: Vec<Box<dyn Iterator<Item = Result<T,Error>>>>,
-            offsets_vec: Vec<usize>,
+// Skips the first `offset` items of `iter` (negative: pads its front with `fill`).
+fn offset_stream<T>(iter: Box<dyn Iterator<Item = Result<T,Error>>>, offset: isize, fill: T) -> Box<dyn Iterator<Item = Result<T,Error>>>
+where T: Clone + 'static
+{
+    if offset < 0 {
+        return Box::new(std::iter::repeat(fill).take(offset.unsigned_abs()).map(Ok).chain(iter));
+    }
+
+    let mut to_skip = offset as usize;
+    Box::new(iter.filter(move |item| {
+        // upstream errors are never skipped
+        if to_skip > 0 && item.is_ok() {
+            to_skip -= 1;
+            return false;
+        }
+        true
+    }))
+}
+
+/// https://more-itertools.readthedocs.io/en/v10.2.0/api.html#more_itertools.zip_offset
+///
+/// Zips the inputs, each one shifted by its offset: a positive offset skips the
+/// first items of an input, a negative one delays it behind `fillvalue`s.
+pub fn zip_offset<T>(iter_vec: Vec<Box<dyn Iterator<Item = Result<T,Error>>>>,
+            offsets_vec: Vec<isize>,
             longest: bool,
             fillvalue: T) -> Box<dyn Iterator<Item = Result<Vec<T>,Error>>> 
 where T: Clone + 'static            
 {
-    for (index, offset) in offsets_vec.iter().enumerate() {
-        take(iter_vec.get_mut(index).as_mut().unwrap(), *offset);
+    let fillvalues = vec![fillvalue; iter_vec.len()];
+    zip_offset_fills(iter_vec, offsets_vec, longest, fillvalues)
+}
+
+/// Like `zip_offset` with a distinct fill value per input, used both for the
+/// padding of negative offsets and, with `longest`, after the input ends.
+pub fn zip_offset_fills<T>(iter_vec: Vec<Box<dyn Iterator<Item = Result<T,Error>>>>,
+            offsets_vec: Vec<isize>,
+            longest: bool,
+            fillvalues: Vec<T>) -> Box<dyn Iterator<Item = Result<Vec<T>,Error>>> 
+where T: Clone + 'static            
+{
+    let mut init_error = None;
+    if offsets_vec.len() != iter_vec.len() || fillvalues.len() != iter_vec.len() {
+        init_error = Some(error::value_error("[zip_offset] must have one offset and one fill value per input".to_string()));
     }
 
-    return Box::new(ZipOffset {
+    let iter_vec = iter_vec.into_iter().zip(offsets_vec).zip(fillvalues.iter().cloned())
+        .map(|((iter, offset), fill)| offset_stream(iter, offset, fill))
+        .collect();
+
+    Box::new(ZipOffset {
         buf: VecDeque::new(),
         buf2: VecDeque::new(),
         iter_vec,
         iter_finished: false,
         longest,
-        fillvalue
-    });
+        fillvalues,
+        init_error
+    })
 }
 
 
@@ -115,7 +152,7 @@ where T: Clone + 'static
 #[cfg(test)]
 mod tests {
 
-    use crate::{error, utils::{extract_value_from_result_vec, generate_okok_iterator, generate_okokerr_iterator}};
+    use crate::utils::{extract_value_from_result_vec, generate_okok_iterator, generate_okokerr_iterator};
 
     use super::*;
 
@@ -188,5 +225,27 @@ mod tests {
         assert_eq!(vec![vec!['0', 'b'], vec!['1', 'c'], vec!['2', 'd'], vec!['3', 'e']], ret2.0);
         assert_eq!(None, ret2.1);
     }
-}
 
+    #[test]
+    fn test4_negative_offset() {
+        let v = vec![generate_okok_iterator("0123".chars().collect::<Vec<_>>()),
+                    generate_okok_iterator("abc".chars().collect::<Vec<_>>())];
+        let ret = zip_offset(v, vec![0, -1], false, '9');
+        assert_eq!(vec![vec!['0', '9'], vec!['1', 'a'], vec!['2', 'b'], vec!['3', 'c']],
+            extract_value_from_result_vec(ret.collect::<Vec<_>>()).0);
+    }
+
+    #[test]
+    fn test5_fills() {
+        let v = vec![generate_okok_iterator(vec![1, 2, 3]),
+                    generate_okok_iterator(vec![10, 20])];
+        let ret = zip_offset_fills(v, vec![1, -2], true, vec![-1, 0]);
+        assert_eq!(vec![vec![2, 0], vec![3, 0], vec![-1, 10], vec![-1, 20]],
+            extract_value_from_result_vec(ret.collect::<Vec<_>>()).0);
+
+        let v = vec![generate_okok_iterator(vec![1, 2, 3])];
+        let mut ret = zip_offset_fills(v, vec![0, 1], true, vec![0]);
+        assert_eq!(error::Kind::ValueError, ret.next().unwrap().err().unwrap().kind());
+        assert_eq!(None, ret.next());
+    }
+}
\ No newline at end of file
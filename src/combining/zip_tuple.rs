@@ -0,0 +1,280 @@
+use crate::combining::zip_equal::length_mismatch_error;
+use crate::error::Error;
+
+/// A tuple of up to 12 `Result` iterators of possibly different item types,
+/// zipped by `zip_tuple`, `zip_longest_tuple` and `zip_equal_tuple`.
+pub trait ZipSources {
+    const ARITY: usize;
+
+    /// One item of every input, e.g. `(T0, T1)`.
+    type Item;
+    /// One optional item of every input, `None` where the input has ended.
+    type Longest;
+
+    /// Pulls every input once, stopping at the first error.
+    fn next_row(&mut self) -> Result<Self::Longest, Error>;
+    /// Positions of the inputs that have ended in `row`.
+    fn ended(row: &Self::Longest) -> Vec<usize>;
+    /// Unwraps a row; `None` if some input has ended.
+    fn unwrap_row(row: Self::Longest) -> Option<Self::Item>;
+}
+
+macro_rules! impl_zip_sources_for_tuple {
+    ($count:expr; $($idx:tt $t:ident),+) => {
+        impl<$($t),+> ZipSources for ($(Box<dyn Iterator<Item = Result<$t,Error>>>,)+) {
+            const ARITY: usize = $count;
+
+            type Item = ($($t,)+);
+            type Longest = ($(Option<$t>,)+);
+
+            fn next_row(&mut self) -> Result<Self::Longest, Error> {
+                Ok(($(
+                    match self.$idx.next() {
+                        None => None,
+                        Some(Ok(v)) => Some(v),
+                        Some(Err(err)) => { return Err(err); }
+                    },
+                )+))
+            }
+
+            fn ended(row: &Self::Longest) -> Vec<usize> {
+                let mut ret = Vec::new();
+                $(if row.$idx.is_none() { ret.push($idx); })+
+                ret
+            }
+
+            fn unwrap_row(row: Self::Longest) -> Option<Self::Item> {
+                Some(($(row.$idx?,)+))
+            }
+        }
+    };
+}
+
+impl_zip_sources_for_tuple!(1; 0 T0);
+impl_zip_sources_for_tuple!(2; 0 T0, 1 T1);
+impl_zip_sources_for_tuple!(3; 0 T0, 1 T1, 2 T2);
+impl_zip_sources_for_tuple!(4; 0 T0, 1 T1, 2 T2, 3 T3);
+impl_zip_sources_for_tuple!(5; 0 T0, 1 T1, 2 T2, 3 T3, 4 T4);
+impl_zip_sources_for_tuple!(6; 0 T0, 1 T1, 2 T2, 3 T3, 4 T4, 5 T5);
+impl_zip_sources_for_tuple!(7; 0 T0, 1 T1, 2 T2, 3 T3, 4 T4, 5 T5, 6 T6);
+impl_zip_sources_for_tuple!(8; 0 T0, 1 T1, 2 T2, 3 T3, 4 T4, 5 T5, 6 T6, 7 T7);
+impl_zip_sources_for_tuple!(9; 0 T0, 1 T1, 2 T2, 3 T3, 4 T4, 5 T5, 6 T6, 7 T7, 8 T8);
+impl_zip_sources_for_tuple!(10; 0 T0, 1 T1, 2 T2, 3 T3, 4 T4, 5 T5, 6 T6, 7 T7, 8 T8, 9 T9);
+impl_zip_sources_for_tuple!(11; 0 T0, 1 T1, 2 T2, 3 T3, 4 T4, 5 T5, 6 T6, 7 T7, 8 T8, 9 T9, 10 T10);
+impl_zip_sources_for_tuple!(12; 0 T0, 1 T1, 2 T2, 3 T3, 4 T4, 5 T5, 6 T6, 7 T7, 8 T8, 9 T9, 10 T10, 11 T11);
+
+#[derive(Clone, Copy, Debug, PartialEq)]
+enum ZipMode {
+    Shortest,
+    Longest,
+    Equal
+}
+
+struct ZipTuple<S> {
+    sources: S,
+    mode: ZipMode,
+    // number of inputs that can end; the zip is over once they all have
+    finite: usize,
+    iter_finished: bool
+}
+
+impl<S> ZipTuple<S>
+where S: ZipSources
+{
+    // The next row with the `None`s allowed by the mode, or None at the end.
+    fn step(&mut self) -> Option<Result<S::Longest, Error>> {
+        let row = match self.sources.next_row() {
+            Ok(row) => row,
+            Err(err) => { return Some(Err(err)); }
+        };
+
+        let ended = S::ended(&row);
+        if ended.is_empty() {
+            return Some(Ok(row));
+        }
+        if ended.len() == self.finite {
+            return None;
+        }
+
+        match self.mode {
+            ZipMode::Shortest => None,
+            ZipMode::Longest => Some(Ok(row)),
+            ZipMode::Equal => Some(Err(length_mismatch_error(&ended)))
+        }
+    }
+
+    fn next_row(&mut self) -> Option<Result<S::Longest, Error>> {
+        if self.iter_finished {
+            return None;
+        }
+
+        let ret = self.step();
+        if !matches!(ret, Some(Ok(_))) {
+            self.iter_finished = true;
+        }
+        ret
+    }
+}
+
+struct ZipLongestTuple<S> {
+    inner: ZipTuple<S>
+}
+
+impl<S> Iterator for ZipTuple<S>
+where S: ZipSources
+{
+    type Item = Result<S::Item,Error>;
+
+    fn next(&mut self) -> Option<Self::Item> {
+        self.next_row().map(|row| row.map(|row| S::unwrap_row(row).unwrap()))
+    }
+}
+
+impl<S> Iterator for ZipLongestTuple<S>
+where S: ZipSources
+{
+    type Item = Result<S::Longest,Error>;
+
+    fn next(&mut self) -> Option<Self::Item> {
+        self.inner.next_row()
+    }
+}
+
+/// Zips a tuple of `Result` iterators of different item types into tuples,
+/// stopping at the shortest input. See also the `zip!` macro.
+pub fn zip_tuple<S>(sources: S) -> Box<dyn Iterator<Item = Result<S::Item,Error>>>
+where
+S: ZipSources + 'static,
+S::Item: 'static
+{
+    Box::new(ZipTuple {
+        sources,
+        mode: ZipMode::Shortest,
+        finite: S::ARITY,
+        iter_finished: false
+    })
+}
+
+/// Like `zip_tuple` but runs until the longest input ends, with `None` in
+/// the slots of the inputs already ended. See also the `zip_longest!` macro.
+pub fn zip_longest_tuple<S>(sources: S) -> Box<dyn Iterator<Item = Result<S::Longest,Error>>>
+where
+S: ZipSources + 'static,
+S::Longest: 'static
+{
+    Box::new(ZipLongestTuple {
+        inner: ZipTuple {
+            sources,
+            mode: ZipMode::Longest,
+            finite: S::ARITY,
+            iter_finished: false
+        }
+    })
+}
+
+/// Like `zip_tuple` but fails with `Kind::IteratorError`, naming the inputs
+/// that ended first, when the inputs have different lengths. See also the
+/// `zip_equal!` macro.
+pub fn zip_equal_tuple<S>(sources: S) -> Box<dyn Iterator<Item = Result<S::Item,Error>>>
+where
+S: ZipSources + 'static,
+S::Item: 'static
+{
+    Box::new(ZipTuple {
+        sources,
+        mode: ZipMode::Equal,
+        finite: S::ARITY,
+        iter_finished: false
+    })
+}
+
+// Zips `sources` of which only `finite` inputs can end, the others repeating forever.
+pub(crate) fn zip_tuple_bounded<S>(sources: S, strict: bool, finite: usize) -> Box<dyn Iterator<Item = Result<S::Item,Error>>>
+where
+S: ZipSources + 'static,
+S::Item: 'static
+{
+    Box::new(ZipTuple {
+        sources,
+        mode: if strict { ZipMode::Equal } else { ZipMode::Shortest },
+        finite,
+        iter_finished: false
+    })
+}
+
+/// `zip!(a, b, c)` zips up to 12 `Result` iterators into tuples, stopping at the shortest.
+#[macro_export]
+macro_rules! zip {
+    ($($iter:expr),+ $(,)?) => {
+        $crate::combining::zip_tuple::zip_tuple(($($iter,)+))
+    };
+}
+
+/// `zip_longest!(a, b, c)` yields `(Option<T0>, Option<T1>, Option<T2>)` until the longest input ends.
+#[macro_export]
+macro_rules! zip_longest {
+    ($($iter:expr),+ $(,)?) => {
+        $crate::combining::zip_tuple::zip_longest_tuple(($($iter,)+))
+    };
+}
+
+/// `zip_equal!(a, b, c)` zips into tuples and fails if the inputs have different lengths.
+#[macro_export]
+macro_rules! zip_equal {
+    ($($iter:expr),+ $(,)?) => {
+        $crate::combining::zip_tuple::zip_equal_tuple(($($iter,)+))
+    };
+}
+
+#[cfg(test)]
+mod tests {
+    use crate::error;
+    use crate::utils::{extract_value_from_result_vec, generate_okok_iterator, generate_okokerr_iterator};
+
+    #[test]
+    fn test_zip() {
+        let ret = zip!(generate_okok_iterator(vec![1, 2, 3]),
+                       generate_okok_iterator(vec!['a', 'b']),
+                       generate_okok_iterator(vec!["x".to_string(), "y".to_string(), "z".to_string()]));
+        assert_eq!(vec![(1, 'a', "x".to_string()), (2, 'b', "y".to_string())],
+            extract_value_from_result_vec(ret.collect::<Vec<_>>()).0);
+
+        let ret = zip!(generate_okok_iterator(vec![1u8]), generate_okok_iterator(vec![2u16]), generate_okok_iterator(vec![3u32]),
+                       generate_okok_iterator(vec![4u64]), generate_okok_iterator(vec![5i8]), generate_okok_iterator(vec![6i16]),
+                       generate_okok_iterator(vec![7i32]), generate_okok_iterator(vec![8i64]), generate_okok_iterator(vec!['9']),
+                       generate_okok_iterator(vec![10usize]), generate_okok_iterator(vec![11isize]), generate_okok_iterator(vec![true]));
+        assert_eq!(vec![(1u8, 2u16, 3u32, 4u64, 5i8, 6i16, 7i32, 8i64, '9', 10usize, 11isize, true)],
+            extract_value_from_result_vec(ret.collect::<Vec<_>>()).0);
+    }
+
+    #[test]
+    fn test_zip_longest() {
+        let ret = zip_longest!(generate_okok_iterator(vec![1, 2, 3]), generate_okok_iterator(vec!['a']));
+        assert_eq!(vec![(Some(1), Some('a')), (Some(2), None), (Some(3), None)],
+            extract_value_from_result_vec(ret.collect::<Vec<_>>()).0);
+    }
+
+    #[test]
+    fn test_zip_equal() {
+        let ret = zip_equal!(generate_okok_iterator(vec![1, 2]), generate_okok_iterator(vec!['a', 'b']));
+        assert_eq!(vec![(1, 'a'), (2, 'b')], extract_value_from_result_vec(ret.collect::<Vec<_>>()).0);
+
+        let mut ret = zip_equal!(generate_okok_iterator(vec![1, 2]),
+                                 generate_okok_iterator(vec!['a']),
+                                 generate_okok_iterator(vec![0.5, 1.5]));
+        assert_eq!(Some(Ok((1, 'a', 0.5))), ret.next());
+        let err = ret.next().unwrap().err().unwrap();
+        assert_eq!(error::Kind::IteratorError, err.kind());
+        assert_eq!("Iterables have different lengths: input 1 ended early", err.message().unwrap());
+        assert_eq!(None, ret.next());
+    }
+
+    #[test]
+    fn test_error() {
+        let mut ret = zip_longest!(generate_okok_iterator(vec![1, 2, 3]),
+                                   generate_okokerr_iterator(vec!['a'], error::overflow_error("[test]".to_string())));
+        assert_eq!(Some(Ok((Some(1), Some('a')))), ret.next());
+        assert_eq!(error::Kind::OverflowError, ret.next().unwrap().err().unwrap().kind());
+        assert_eq!(None, ret.next());
+    }
+}
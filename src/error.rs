@@ -67,6 +67,8 @@ pub enum Kind {
 
     IteratorError,
 
+    IOError,
+
     None
 }
 
@@ -78,6 +80,10 @@ pub(crate) fn overflow_error(e: String) -> Error {
     Error::new(Kind::OverflowError, Some(e))
 }
 
+pub(crate) fn io_error(e: String) -> Error {
+    Error::new(Kind::IOError, Some(e))
+}
+
 // pub(crate) fn type_error(e: String) -> Error {
 //     Error::new(Kind::TypeError, Some(e))
 // }
@@ -1,6 +1,7 @@
 use crate::{error, sequence::Sequence};
+use crate::grouping::splitter::Splitter;
 
-use super::divide::{divide, Divide};
+use super::divide::{divide, divide_sizes, materialize, Divide};
 
 pub struct ChunkedEven<T> {
     dist: Divide<T>,
@@ -49,9 +50,47 @@ T: Clone + 'static
     }
 }
 
+/// Like `chunked_even`, but reads `iter` lazily, one chunk at a time, using
+/// only its length. A `Kind::ValueError` follows the chunks if `iter` does not
+/// have the items its length announced.
+pub fn chunked_even_iter<T, I>(iter: I, bucket_cnt: usize) -> Box<dyn Iterator<Item = Result<Vec<T>, error::Error>>>
+where
+T: 'static,
+I: ExactSizeIterator<Item = Result<T, error::Error>> + 'static
+{
+    let len = iter.len();
+    chunked_even_len(Box::new(iter), len, bucket_cnt)
+}
+
+/// Like `chunked_even_iter`, for an input of unknown length: it is first read
+/// into memory, up to its first error, which follows the chunks. `iter` must be finite.
+pub fn chunked_even_materialized<T>(iter: Box<dyn Iterator<Item = Result<T, error::Error>>>, bucket_cnt: usize) -> Box<dyn Iterator<Item = Result<Vec<T>, error::Error>>>
+where
+T: 'static
+{
+    if bucket_cnt == 0 {
+        return Box::new(std::iter::empty());
+    }
+
+    let (iter, len) = materialize(iter);
+    chunked_even_len(iter, len, bucket_cnt)
+}
+
+fn chunked_even_len<T>(iter: Box<dyn Iterator<Item = Result<T, error::Error>>>, len: usize, bucket_cnt: usize) -> Box<dyn Iterator<Item = Result<Vec<T>, error::Error>>>
+where
+T: 'static
+{
+    if bucket_cnt == 0 {
+        return Box::new(std::iter::empty());
+    }
+
+    Splitter::sizes(iter, divide_sizes(len, bucket_cnt)).with_name("chunked_even").strict(true).split_vec()
+}
+
 #[cfg(test)]
 mod tests {
     use crate::sequence::create_seq_from_vec;
+    use crate::utils::{extract_value_from_result_vec, generate_okok_len_iterator, generate_okokerr_iterator};
 
     use super::*;
 
@@ -67,4 +106,26 @@ mod tests {
         assert_eq!(None, ce.next());
         assert_eq!(None, ce.next());
     }
-}
\ No newline at end of file
+
+    #[test]
+    fn test2_iter() {
+        let ce = chunked_even_iter(vec![1,2,3,4,5,6,7,8,9,10].into_iter().map(Ok), 3);
+        assert_eq!(vec![vec![1, 2, 3, 4], vec![5, 6, 7], vec![8, 9, 10]], extract_value_from_result_vec(ce.collect::<Vec<_>>()).0);
+
+        let ce = chunked_even_materialized(Box::new((1..=10).filter(|x| x % 2 == 0).map(Ok)), 2);
+        assert_eq!(vec![vec![2, 4, 6], vec![8, 10]], extract_value_from_result_vec(ce.collect::<Vec<_>>()).0);
+
+        let ce = chunked_even_materialized(generate_okokerr_iterator(vec![1,2,3], error::overflow_error("[test]".to_string())), 2);
+        let ret = extract_value_from_result_vec(ce.collect::<Vec<_>>());
+        assert_eq!(vec![vec![1, 2], vec![3]], ret.0);
+        assert_eq!(error::Kind::OverflowError, ret.1.unwrap().kind());
+    }
+
+    #[test]
+    fn test3_iter_wrong_len() {
+        let ce = chunked_even_iter(generate_okok_len_iterator(vec![1, 2, 3], 4), 2);
+        let ret = extract_value_from_result_vec(ce.collect::<Vec<_>>());
+        assert_eq!(vec![vec![1, 2], vec![3]], ret.0);
+        assert_eq!("[chunked_even] the input is shorter than the sizes", ret.1.unwrap().message().unwrap());
+    }
+}
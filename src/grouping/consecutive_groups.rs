@@ -0,0 +1,51 @@
+use crate::error::Error;
+use crate::itertools::groupby::{groupby_with, Group};
+
+/// https://more-itertools.readthedocs.io/en/v10.2.0/api.html#more_itertools.consecutive_groups
+///
+/// `ordering` maps each item to its position on the integer line (e.g. the
+/// day number of a date); runs where it increases by one form a group.
+pub fn consecutive_groups<T>(iter: Box<dyn Iterator<Item = Result<T,Error>>>, ordering: fn(&T) -> i128) -> Box<dyn Iterator<Item = Result<Group<T, i128>, Error>>>
+where T: 'static
+{
+    let mut index: i128 = 0;
+    let key_fn = move |x: &T| {
+        let key = ordering(x) - index;
+        index += 1;
+        key
+    };
+
+    Box::new(groupby_with(iter, Box::new(key_fn)).map(|x| x.map(|(_, group)| group)))
+}
+
+
+#[cfg(test)]
+mod tests {
+    use crate::error;
+    use crate::utils::{generate_okok_iterator, generate_okokerr_iterator};
+
+    use super::*;
+
+    #[test]
+    fn test1() {
+        let ret = consecutive_groups(generate_okok_iterator(vec![1, 10, 11, 12, 20, 30, 31, 32, 33, 40]), |x| *x);
+        let v = ret.map(|g| g.ok().unwrap().collect::<Vec<_>>()).collect::<Vec<_>>();
+        assert_eq!(vec![vec![1], vec![10, 11, 12], vec![20], vec![30, 31, 32, 33], vec![40]], v);
+    }
+
+    #[test]
+    fn test2_ordering() {
+        let ret = consecutive_groups(generate_okok_iterator("abcdfgilmnop".chars().collect::<Vec<_>>()), |x| *x as i128);
+        let v = ret.map(|g| g.ok().unwrap().collect::<String>()).collect::<Vec<_>>();
+        assert_eq!(vec!["abcd".to_string(), "fg".to_string(), "i".to_string(), "lmnop".to_string()], v);
+    }
+
+    #[test]
+    fn test3_error() {
+        let mut ret = consecutive_groups(generate_okokerr_iterator(vec![1, 2, 4], error::overflow_error("[test]".to_string())), |x| *x);
+        assert_eq!(vec![1, 2], ret.next().unwrap().ok().unwrap().collect::<Vec<_>>());
+        assert_eq!(vec![4], ret.next().unwrap().ok().unwrap().collect::<Vec<_>>());
+        assert_eq!(error::Kind::OverflowError, ret.next().unwrap().err().unwrap().kind());
+        assert!(ret.next().is_none());
+    }
+}
@@ -1,3 +1,5 @@
+use std::cell::RefCell;
+use std::collections::VecDeque;
 use std::rc::Rc;
 
 use crate::error;
@@ -93,9 +95,166 @@ T: Clone + PartialEq + 'static
 }
 
 
+/// How `BucketsInner` assigns the items read to the buckets.
+pub(crate) enum Assign {
+    RoundRobin,
+    // cumulative ends of consecutive ranges; the input must end at the last one
+    Consecutive(Vec<usize>)
+}
+
+pub(crate) struct BucketsInner<T> {
+    iter: Box<dyn Iterator<Item = Result<T,Error>>>,
+    assign: Assign,
+    // number of items read
+    read: usize,
+    queues: Vec<VecDeque<T>>,
+    // buckets not dropped yet; the items of dropped ones are discarded
+    alive: Vec<bool>,
+    iter_finished: bool,
+    iter_error: Option<Error>
+}
+
+impl<T> BucketsInner<T> {
+    fn bucket_of(&self, index: usize) -> usize {
+        match &self.assign {
+            Assign::RoundRobin => index % self.queues.len(),
+            Assign::Consecutive(ends) => ends.partition_point(|end| *end <= index)
+        }
+    }
+
+    // True once no more items can go to the bucket; the last one still
+    // reports an error right after its range.
+    fn is_complete(&self, no: usize) -> bool {
+        match &self.assign {
+            Assign::RoundRobin => false,
+            Assign::Consecutive(ends) => no + 1 < ends.len() && self.read >= ends[no]
+        }
+    }
+
+    // True if the input ended before the last range was filled.
+    fn is_short(&self) -> bool {
+        match &self.assign {
+            Assign::RoundRobin => false,
+            Assign::Consecutive(ends) => ends.last().is_some_and(|end| self.read < *end)
+        }
+    }
+}
+
+struct BucketCursor<T> {
+    no: usize,
+    inner: Rc<RefCell<BucketsInner<T>>>,
+    iter_finished: bool
+}
+
+impl<T> Iterator for BucketCursor<T> {
+    type Item = Result<T,Error>;
+
+    fn next(&mut self) -> Option<Self::Item> {
+        if self.iter_finished {
+            return None;
+        }
+
+        let mut inner = self.inner.borrow_mut();
+        loop {
+            if let Some(v) = inner.queues[self.no].pop_front() {
+                return Some(Ok(v));
+            }
+
+            if inner.is_complete(self.no) {
+                self.iter_finished = true;
+                return None;
+            }
+
+            if let Some(err) = inner.iter_error.as_ref() {
+                self.iter_finished = true;
+                return Some(Err(err.clone()));
+            }
+
+            if inner.iter_finished {
+                self.iter_finished = true;
+                return None;
+            }
+
+            let no = inner.bucket_of(inner.read);
+            if no >= inner.queues.len() {
+                // past the last range: only read to check that the input ends there
+                match inner.iter.next() {
+                    None => {},
+                    Some(Err(err)) => { inner.iter_error = Some(err); },
+                    Some(Ok(_)) => {
+                        inner.iter_error = Some(error::value_error("[divide_iter] the input is longer than its length".to_string()));
+                    }
+                }
+                inner.iter_finished = true;
+                continue;
+            }
+
+            match inner.iter.next() {
+                None => {
+                    if inner.is_short() {
+                        inner.iter_error = Some(error::value_error("[divide_iter] the input is shorter than its length".to_string()));
+                    }
+                    inner.iter_finished = true;
+                },
+                Some(Err(err)) => { inner.iter_error = Some(err); },
+                Some(Ok(v)) => {
+                    inner.read += 1;
+                    if inner.alive[no] {
+                        inner.queues[no].push_back(v);
+                    }
+                }
+            }
+        }
+    }
+}
+
+impl<T> Drop for BucketCursor<T> {
+    fn drop(&mut self) {
+        let mut inner = self.inner.borrow_mut();
+        inner.alive[self.no] = false;
+        inner.queues[self.no].clear();
+    }
+}
+
+/// One iterator per bucket over the shared `iter`. Items are queued until
+/// their bucket reads them; an upstream error is yielded by each bucket that
+/// could still receive items.
+pub(crate) fn buckets<T>(iter: Box<dyn Iterator<Item = Result<T,Error>>>, assign: Assign, bucket_count: usize) -> Vec<Box<dyn Iterator<Item = Result<T,Error>>>>
+where T: 'static
+{
+    let inner = Rc::new(RefCell::new(BucketsInner {
+        iter,
+        assign,
+        read: 0,
+        queues: (0..bucket_count).map(|_| VecDeque::new()).collect(),
+        alive: vec![true; bucket_count],
+        iter_finished: false,
+        iter_error: None
+    }));
+
+    (0..bucket_count).map(|no| {
+        Box::new(BucketCursor {
+            no,
+            inner: Rc::clone(&inner),
+            iter_finished: false
+        }) as Box<dyn Iterator<Item = Result<T,Error>>>
+    }).collect()
+}
+
+/// https://more-itertools.readthedocs.io/en/v10.2.0/api.html#more_itertools.distribute
+///
+/// Like `distribute`, but reads `iter` lazily: item `i` goes to bucket
+/// `i % bucket_count`, queued until that bucket reads it.
+pub fn distribute_iter<T>(iter: Box<dyn Iterator<Item = Result<T,Error>>>, bucket_count: usize) -> Vec<Box<dyn Iterator<Item = Result<T,Error>>>>
+where T: 'static
+{
+    buckets(iter, Assign::RoundRobin, bucket_count)
+}
+
 #[cfg(test)]
 mod tests {
     use crate::sequence::create_seq_from_vec;
+    use crate::utils::{extract_value_from_result_vec, generate_okok_iterator, generate_okokerr_iterator};
 
     use super::*;
 
@@ -158,4 +317,32 @@ mod tests {
         assert_eq!(None, cur_3.next());
         assert_eq!(None, cur_4.next());
     }
+
+    #[test]
+    fn test4_iter() {
+        let mut dist = distribute_iter(generate_okok_iterator(vec![1,2,3,4,5,6,7]), 3);
+        assert_eq!(3, dist.len());
+        assert_eq!(vec![3, 6], extract_value_from_result_vec(dist.pop().unwrap().collect::<Vec<_>>()).0);
+        assert_eq!(vec![1, 4, 7], extract_value_from_result_vec(dist.remove(0).collect::<Vec<_>>()).0);
+        assert_eq!(vec![2, 5], extract_value_from_result_vec(dist.remove(0).collect::<Vec<_>>()).0);
+
+        // an infinite input
+        let mut dist = distribute_iter(Box::new((0..).map(Ok)), 2);
+        assert_eq!(vec![Ok(1), Ok(3), Ok(5)], dist[1].by_ref().take(3).collect::<Vec<_>>());
+        assert_eq!(vec![Ok(0), Ok(2)], dist[0].by_ref().take(2).collect::<Vec<_>>());
+
+        assert_eq!(0, distribute_iter(generate_okok_iterator(vec![1]), 0).len());
+    }
+
+    #[test]
+    fn test5_iter_error() {
+        let mut dist = distribute_iter(generate_okokerr_iterator(vec![1,2,3], error::overflow_error("[test]".to_string())), 2);
+        let ret = extract_value_from_result_vec(dist[0].by_ref().collect::<Vec<_>>());
+        assert_eq!(vec![1, 3], ret.0);
+        assert_eq!(error::Kind::OverflowError, ret.1.unwrap().kind());
+
+        let ret = extract_value_from_result_vec(dist[1].by_ref().collect::<Vec<_>>());
+        assert_eq!(vec![2], ret.0);
+        assert_eq!(error::Kind::OverflowError, ret.1.unwrap().kind());
+    }
 }
\ No newline at end of file
@@ -2,6 +2,7 @@ use std::rc::Rc;
 
 use crate::error;
 use crate::error::Error;
+use crate::grouping::distribute::{buckets, Assign};
 use crate::itertools::accumulate::accumulate;
 use crate::sequence::Sequence;
 use crate::utils::extract_value_from_result_vec;
@@ -139,9 +140,81 @@ impl<T> Drop for Divide<T> {
 }
 
 
+/// Reads `iter` into memory, up to its first error, to count its items.
+/// `iter` must be finite.
+pub(crate) fn materialize<T>(iter: Box<dyn Iterator<Item = Result<T,Error>>>) -> (Box<dyn Iterator<Item = Result<T,Error>>>, usize)
+where
+T: 'static
+{
+    let mut items = Vec::new();
+    let mut len = 0;
+    for item in iter {
+        let is_err = item.is_err();
+        items.push(item);
+        if is_err {
+            break;
+        }
+        len += 1;
+    }
+    (Box::new(items.into_iter()), len)
+}
+
+/// The lengths of `bucket_count` consecutive parts of `len` items, the first
+/// ones longer by 1 when it does not divide evenly.
+pub(crate) fn divide_sizes(len: usize, bucket_count: usize) -> Vec<usize> {
+    let base = len / bucket_count;
+    let rem = len % bucket_count;
+    (0..bucket_count).map(|i| if i < rem { base + 1 } else { base }).collect()
+}
+
+/// https://more-itertools.readthedocs.io/en/v10.2.0/api.html#more_itertools.divide
+///
+/// Like `divide`, but reads `iter` lazily, using only its length: a part
+/// queues the items of the parts before it only if it is read first. A
+/// `Kind::ValueError` ends the parts still open if `iter` does not have the
+/// items its length announced.
+pub fn divide_iter<T, I>(iter: I, bucket_count: usize) -> Vec<Box<dyn Iterator<Item = Result<T,Error>>>>
+where
+T: 'static,
+I: ExactSizeIterator<Item = Result<T,Error>> + 'static
+{
+    let len = iter.len();
+    divide_len(Box::new(iter), len, bucket_count)
+}
+
+/// Like `divide_iter`, for an input of unknown length: it is first read into
+/// memory, up to its first error, which ends the last part. `iter` must be finite.
+pub fn divide_materialized<T>(iter: Box<dyn Iterator<Item = Result<T,Error>>>, bucket_count: usize) -> Vec<Box<dyn Iterator<Item = Result<T,Error>>>>
+where
+T: 'static
+{
+    if bucket_count == 0 {
+        return Vec::new();
+    }
+
+    let (iter, len) = materialize(iter);
+    divide_len(iter, len, bucket_count)
+}
+
+fn divide_len<T>(iter: Box<dyn Iterator<Item = Result<T,Error>>>, len: usize, bucket_count: usize) -> Vec<Box<dyn Iterator<Item = Result<T,Error>>>>
+where
+T: 'static
+{
+    if bucket_count == 0 {
+        return Vec::new();
+    }
+
+    let ends = divide_sizes(len, bucket_count).into_iter().scan(0, |end, size| {
+        *end += size;
+        Some(*end)
+    }).collect();
+    buckets(iter, Assign::Consecutive(ends), bucket_count)
+}
+
 #[cfg(test)]
 mod tests {
     use crate::sequence::create_seq_from_vec;
+    use crate::utils::{generate_okok_iterator, generate_okok_len_iterator, generate_okokerr_iterator};
 
     use super::*;
 
@@ -180,4 +253,50 @@ mod tests {
         let mut cur_0 = div.iter(0);
         println!("{:?}", cur_0.next());
     }
+
+    #[test]
+    fn test3_iter() {
+        let mut div = divide_iter(vec![1,2,3,4,5,6,7].into_iter().map(Ok), 3);
+        assert_eq!(vec![6, 7], extract_value_from_result_vec(div.pop().unwrap().collect::<Vec<_>>()).0);
+        assert_eq!(vec![1, 2, 3], extract_value_from_result_vec(div.remove(0).collect::<Vec<_>>()).0);
+        assert_eq!(vec![4, 5], extract_value_from_result_vec(div.remove(0).collect::<Vec<_>>()).0);
+
+        // without a known length the input is read first
+        let div = divide_materialized(Box::new(vec![1,2,3,4,5].into_iter().filter(|x| x % 2 == 1).map(Ok)), 2);
+        let ret = div.into_iter().map(|it| extract_value_from_result_vec(it.collect::<Vec<_>>()).0).collect::<Vec<_>>();
+        assert_eq!(vec![vec![1, 3], vec![5]], ret);
+
+        let div = divide_iter(vec![1, 2].into_iter().map(Ok), 4);
+        let ret = div.into_iter().map(|it| extract_value_from_result_vec(it.collect::<Vec<_>>()).0).collect::<Vec<_>>();
+        assert_eq!(vec![vec![1], vec![2], vec![], vec![]], ret);
+
+        assert_eq!(0, divide_iter(vec![1].into_iter().map(Ok), 0).len());
+        assert_eq!(0, divide_materialized(generate_okok_iterator(vec![1]), 0).len());
+    }
+
+    #[test]
+    fn test4_iter_error() {
+        let div = divide_materialized(generate_okokerr_iterator(vec![1,2,3,4], error::overflow_error("[test]".to_string())), 2);
+        let ret = div.into_iter().map(|it| extract_value_from_result_vec(it.collect::<Vec<_>>())).collect::<Vec<_>>();
+        assert_eq!(vec![1, 2], ret[0].0);
+        assert!(ret[0].1.is_none());
+        assert_eq!(vec![3, 4], ret[1].0);
+        assert_eq!(error::Kind::OverflowError, ret[1].1.as_ref().unwrap().kind());
+    }
+
+    #[test]
+    fn test5_iter_wrong_len() {
+        let div = divide_iter(generate_okok_len_iterator(vec![1, 2, 3, 4, 5], 4), 2);
+        let ret = div.into_iter().map(|it| extract_value_from_result_vec(it.collect::<Vec<_>>())).collect::<Vec<_>>();
+        assert_eq!(vec![1, 2], ret[0].0);
+        assert!(ret[0].1.is_none());
+        assert_eq!(vec![3, 4], ret[1].0);
+        assert_eq!("[divide_iter] the input is longer than its length", ret[1].1.as_ref().unwrap().message().unwrap());
+
+        let div = divide_iter(generate_okok_len_iterator(vec![1, 2, 3], 4), 2);
+        let ret = div.into_iter().map(|it| extract_value_from_result_vec(it.collect::<Vec<_>>())).collect::<Vec<_>>();
+        assert_eq!(vec![1, 2], ret[0].0);
+        assert_eq!(vec![3], ret[1].0);
+        assert_eq!(error::Kind::ValueError, ret[1].1.as_ref().unwrap().kind());
+    }
 }
@@ -0,0 +1,211 @@
+use std::collections::{BTreeMap, HashMap};
+use std::fmt::Debug;
+use std::hash::Hash;
+
+use crate::error::{self, Error};
+
+trait Groups<K, V>: Default + IntoIterator<Item = (K, Vec<V>)> {
+    fn push_value(&mut self, key: K, value: V);
+}
+
+impl<K, V> Groups<K, V> for HashMap<K, Vec<V>>
+where K: Hash + Eq
+{
+    fn push_value(&mut self, key: K, value: V) {
+        self.entry(key).or_default().push(value);
+    }
+}
+
+impl<K, V> Groups<K, V> for BTreeMap<K, Vec<V>>
+where K: Ord
+{
+    fn push_value(&mut self, key: K, value: V) {
+        self.entry(key).or_default().push(value);
+    }
+}
+
+fn map_reduce_impl<T, K, V, R, G, M>(iter: Box<dyn Iterator<Item = Result<T,Error>>>,
+    keyfunc: impl Fn(&T) -> Result<K,Error>,
+    valuefunc: impl Fn(&K, &T) -> Result<V,Error>,
+    reducefunc: impl Fn(&K, &Vec<V>) -> Result<R,Error>) -> Result<M, Error>
+where
+G: Groups<K, V>,
+M: FromIterator<(K, R)>
+{
+    let mut groups = G::default();
+
+    for item in iter {
+        let item = item?;
+        let key = keyfunc(&item)?;
+        let value = valuefunc(&key, &item)?;
+        groups.push_value(key, value);
+    }
+
+    groups.into_iter().map(|(key, values)| {
+        let reduced = reducefunc(&key, &values)?;
+        Ok((key, reduced))
+    }).collect()
+}
+
+fn key_error<K: Debug>(key: &K, err: Error) -> Error {
+    let message = match err.message() {
+        Some(msg) => format!("[map_reduce] key {:?}: {}", key, msg),
+        None => format!("[map_reduce] key {:?}", key)
+    };
+    error::any_error(err.kind(), message)
+}
+
+/// https://more-itertools.readthedocs.io/en/v10.2.0/api.html#more_itertools.map_reduce
+pub fn map_reduce<T, K, V, R>(iter: Box<dyn Iterator<Item = Result<T,Error>>>,
+    keyfunc: fn(&T) -> K,
+    valuefunc: fn(&T) -> V,
+    reducefunc: fn(&Vec<V>) -> R) -> Result<HashMap<K, R>, Error>
+where K: Hash + Eq
+{
+    map_reduce_impl::<T, K, V, R, HashMap<K, Vec<V>>, _>(iter,
+        |x| Ok(keyfunc(x)),
+        |_, x| Ok(valuefunc(x)),
+        |_, values| Ok(reducefunc(values)))
+}
+
+/// Same as `map_reduce`, but the result is ordered by key.
+pub fn map_reduce_btree<T, K, V, R>(iter: Box<dyn Iterator<Item = Result<T,Error>>>,
+    keyfunc: fn(&T) -> K,
+    valuefunc: fn(&T) -> V,
+    reducefunc: fn(&Vec<V>) -> R) -> Result<BTreeMap<K, R>, Error>
+where K: Ord
+{
+    map_reduce_impl::<T, K, V, R, BTreeMap<K, Vec<V>>, _>(iter,
+        |x| Ok(keyfunc(x)),
+        |_, x| Ok(valuefunc(x)),
+        |_, values| Ok(reducefunc(values)))
+}
+
+/// Fallible `map_reduce`: stops on the first error, naming the key in the message
+/// when the failure happened in `valuefunc` or `reducefunc`.
+pub fn try_map_reduce<T, K, V, R>(iter: Box<dyn Iterator<Item = Result<T,Error>>>,
+    keyfunc: fn(&T) -> Result<K,Error>,
+    valuefunc: fn(&T) -> Result<V,Error>,
+    reducefunc: fn(&Vec<V>) -> Result<R,Error>) -> Result<HashMap<K, R>, Error>
+where K: Hash + Eq + Debug
+{
+    map_reduce_impl::<T, K, V, R, HashMap<K, Vec<V>>, _>(iter,
+        keyfunc,
+        |key, x| valuefunc(x).map_err(|err| key_error(key, err)),
+        |key, values| reducefunc(values).map_err(|err| key_error(key, err)))
+}
+
+/// Same as `try_map_reduce`, but the result is ordered by key.
+pub fn try_map_reduce_btree<T, K, V, R>(iter: Box<dyn Iterator<Item = Result<T,Error>>>,
+    keyfunc: fn(&T) -> Result<K,Error>,
+    valuefunc: fn(&T) -> Result<V,Error>,
+    reducefunc: fn(&Vec<V>) -> Result<R,Error>) -> Result<BTreeMap<K, R>, Error>
+where K: Ord + Debug
+{
+    map_reduce_impl::<T, K, V, R, BTreeMap<K, Vec<V>>, _>(iter,
+        keyfunc,
+        |key, x| valuefunc(x).map_err(|err| key_error(key, err)),
+        |key, values| reducefunc(values).map_err(|err| key_error(key, err)))
+}
+
+
+#[cfg(test)]
+mod tests {
+    use crate::utils::{generate_okok_iterator, generate_okokerr_iterator};
+
+    use super::*;
+
+    #[test]
+    fn test1() {
+        let ret = map_reduce(generate_okok_iterator("abbccc".chars().collect::<Vec<_>>()),
+            |x| x.to_ascii_uppercase(),
+            |_| 1,
+            |x| x.iter().sum::<i32>()).ok().unwrap();
+
+        assert_eq!(3, ret.len());
+        assert_eq!(Some(&1), ret.get(&'A'));
+        assert_eq!(Some(&2), ret.get(&'B'));
+        assert_eq!(Some(&3), ret.get(&'C'));
+    }
+
+    #[test]
+    fn test2_btree() {
+        let ret = map_reduce_btree(generate_okok_iterator(vec![1,2,3,4,5,6,7,8,9]),
+            |x| x % 3,
+            |x| *x,
+            |x| x.clone()).ok().unwrap();
+
+        assert_eq!(vec![(0, vec![3,6,9]), (1, vec![1,4,7]), (2, vec![2,5,8])], ret.into_iter().collect::<Vec<_>>());
+    }
+
+    #[test]
+    fn test3_upstream_error() {
+        let ret = map_reduce(generate_okokerr_iterator(vec![1,2,3], error::overflow_error("[test]".to_string())),
+            |x| x % 2,
+            |x| *x,
+            |x| x.len());
+        assert_eq!(error::Kind::OverflowError, ret.err().unwrap().kind());
+    }
+
+    #[test]
+    fn test4_try() {
+        let ret = try_map_reduce_btree(generate_okok_iterator(vec![1,2,3,4]),
+            |x| Ok(x % 2),
+            |x| Ok(*x),
+            |x| Ok(x.iter().sum::<i32>())).ok().unwrap();
+        assert_eq!(vec![(0, 6), (1, 4)], ret.into_iter().collect::<Vec<_>>());
+
+        let ret = try_map_reduce(generate_okok_iterator(vec![1,2,3,4]),
+            |x| Ok(x % 2),
+            |x| Ok(*x),
+            |x| {
+                if x.len() > 1 && x[0] % 2 == 0 {
+                    return Err(error::value_error("even".to_string()));
+                }
+                Ok(x.len())
+            });
+        let err = ret.err().unwrap();
+        assert_eq!(error::Kind::ValueError, err.kind());
+        assert_eq!("[map_reduce] key 0: even", err.message().unwrap());
+
+        let ret = try_map_reduce_btree(generate_okok_iterator(vec![1,2,3,4]),
+            |x| Ok(x % 2),
+            |x| {
+                if *x == 3 {
+                    return Err(error::overflow_error("three".to_string()));
+                }
+                Ok(*x)
+            },
+            |x| Ok(x.len()));
+        let err = ret.err().unwrap();
+        assert_eq!(error::Kind::OverflowError, err.kind());
+        assert_eq!("[map_reduce] key 1: three", err.message().unwrap());
+
+        let ret = try_map_reduce(generate_okok_iterator(vec![1,2,3,4]),
+            |x| {
+                if *x == 2 {
+                    return Err(error::value_error("bad key".to_string()));
+                }
+                Ok(*x)
+            },
+            |x| Ok(*x),
+            |x| Ok(x.len()));
+        assert_eq!("bad key", ret.err().unwrap().message().unwrap());
+    }
+
+    #[test]
+    fn test5_keyfunc_once() {
+        use std::sync::atomic::{AtomicUsize, Ordering};
+        static KEY_CALLS: AtomicUsize = AtomicUsize::new(0);
+
+        let ret = try_map_reduce(generate_okok_iterator(vec![1,2,3]),
+            |x| {
+                KEY_CALLS.fetch_add(1, Ordering::SeqCst);
+                Ok(*x)
+            },
+            |x| if *x == 2 { Err(error::value_error("two".to_string())) } else { Ok(*x) },
+            |x| Ok(x.len()));
+        assert_eq!("[map_reduce] key 2: two", ret.err().unwrap().message().unwrap());
+        assert_eq!(2, KEY_CALLS.load(Ordering::SeqCst));
+    }
+}
\ No newline at end of file
@@ -24,4 +24,10 @@ pub mod split_into;
 
 pub mod split_before;
 
-pub mod split_after;
\ No newline at end of file
+pub mod split_after;
+
+pub mod map_reduce;
+
+pub mod consecutive_groups;
+
+pub mod splitter;
\ No newline at end of file
@@ -1,5 +1,7 @@
-use crate::{error::Error, others::cache_until::cache_until};
+use crate::error::Error;
+use crate::grouping::splitter::{KeepSeparator, Splitter};
 
+/// Same as `Splitter::at` with `KeepSeparator::AttachToPrevious`; a negative `maxsplit` means no limit.
 pub fn splite_after<T>(iter: Box<dyn Iterator<Item = Result<T,Error>>>, 
     pred: fn(&T) -> Result<bool,Error>,
     maxsplit: i128
@@ -7,14 +9,19 @@ pub fn splite_after<T>(iter: Box<dyn Iterator<Item = Result<T,Error>>>,
 where 
 T: Clone + 'static
 {
-    cache_until(iter, pred, maxsplit, true)
+    let splitter = Splitter::at(iter, pred).with_name("splite_after").keep_separator(KeepSeparator::AttachToPrevious);
+    match usize::try_from(maxsplit) {
+        Ok(maxsplit) => splitter.maxsplit(maxsplit).split_vec(),
+        Err(_) => splitter.split_vec()
+    }
 }
 
 
 #[cfg(test)]
 mod tests {
 
-    use crate::utils::generate_okok_iterator;
+    use crate::error;
+    use crate::utils::{extract_value_from_result_vec, generate_okok_iterator};
 
     use super::*;
 
@@ -41,4 +48,11 @@ mod tests {
         assert_eq!(Some(Ok(vec![0,1,2,3,4,5,6,7,8,9])), r.next());
         assert_eq!(None, r.next());
     }
-}
\ No newline at end of file
+
+    #[test]
+    fn test2_error() {
+        let r = splite_after(generate_okok_iterator(vec![1, 2, 3]), |x| if *x == 2 { Err(error::value_error("bad".to_string())) } else { Ok(false) }, -1);
+        let ret = extract_value_from_result_vec(r.collect::<Vec<_>>());
+        assert_eq!("[splite_after] bad", ret.1.unwrap().message().unwrap());
+    }
+}
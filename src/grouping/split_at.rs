@@ -1,135 +1,22 @@
-use std::collections::LinkedList;
-
-use crate::error::{self, Error};
-
-struct SplitAtOutputItem<T> {
-    is_sep: bool,
-    items: Vec<T>,
-    error: Option<Error>,
-    finished: bool,
-}
-
-
-impl<T> SplitAtOutputItem<T> {
-    pub fn new(is_sep: bool, items: Vec<T>, error: Option<Error>, finished: bool,) -> Self {
-        return SplitAtOutputItem {
-            is_sep,
-            items,
-            error,
-            finished
-        };
-    }
-}
-
-pub struct SplitAt<T> {
-    ret_buf: LinkedList<SplitAtOutputItem<T>>,
-    iter: Box<dyn Iterator<Item = Result<T,Error>>>,
-    pred: fn(&T) -> Result<bool,Error>,
-    maxsplit: i128,
-    keep_separator: bool,
-    splited: i128,
-    iter_finished: bool
-}
-
-impl<T> SplitAt<T> {
-    pub fn set_last_output_item_finished(&mut self) {
-        if self.ret_buf.len() > 0 {
-            self.ret_buf.back_mut().unwrap().finished = true;
-        }
-    }
-}
-
-impl<T> Iterator for SplitAt<T> 
-{
-    type Item = Result<Vec<T>,Error>;
-
-    fn next(&mut self) -> Option<Self::Item> {
-        
-        loop {
-            while self.ret_buf.len() > 0 {
-                if self.ret_buf.front().unwrap().finished {
-                    let ret = self.ret_buf.pop_front();
-                    if ret.as_ref().unwrap().error.is_none() {
-                        if !self.keep_separator && ret.as_ref().unwrap().is_sep {
-                            continue;
-                        }
-                        return Some(Ok(ret.unwrap().items)); 
-                    } else {
-                        return Some(Err(ret.unwrap().error.unwrap()));
-                    }
-                } else {
-                    break; // jump to eating logic
-                }
-            }
-
-            // eating logic
-            if self.iter_finished {
-                return None;
-            }
-
-            if let Some(v) = self.iter.next() {
-                match v {
-                    Ok(ok_v) => {
-                        let pred_ret = (self.pred)(&ok_v);
-                        match pred_ret {
-                            Ok(ok_pred_ret) => {
-                                if ok_pred_ret && (self.maxsplit < 0 || self.splited < self.maxsplit) { // meet a seperator
-                                    self.splited += 1;
-                                    self.set_last_output_item_finished();
-                                    self.ret_buf.push_back(SplitAtOutputItem::new(true, vec![ok_v], None, true));
-                                    self.ret_buf.push_back(SplitAtOutputItem::new(false, Vec::new(), None, false));
-                                } else {
-                                    self.ret_buf.back_mut().unwrap().items.push(ok_v);
-                                }
-                            },
-                            Err(err_pred_ret) => {
-                                self.set_last_output_item_finished();
-                                self.ret_buf.push_back(SplitAtOutputItem::new(false, Vec::new(), Some(error::any_error(err_pred_ret.kind(), "[split_at] ".to_string()+ err_pred_ret.message().unwrap())), true));
-                                self.iter_finished = true;
-                            }
-                        }
-                    },
-                    Err(err_v) => {
-                        self.set_last_output_item_finished();
-                        self.ret_buf.push_back(SplitAtOutputItem::new(false, Vec::new(), Some(err_v), true));
-                        self.iter_finished = true;
-                    }
-                }
-            } else {
-                self.set_last_output_item_finished();
-                self.iter_finished = true;
-                continue;
-            }
-        }
-    }
-}
+use crate::error::Error;
+use crate::grouping::splitter::{KeepSeparator, Splitter};
 
+/// Same as `Splitter::at`; a negative `maxsplit` means no limit.
 pub fn split_at<T>(iter: Box<dyn Iterator<Item = Result<T,Error>>>, pred: fn(&T)->Result<bool,Error>, maxsplit: i128, keep_separator: bool) -> Box<dyn Iterator<Item=Result<Vec<T>,Error>>>
 where
 T: 'static
 {
-    let mut ret = SplitAt {
-        ret_buf: LinkedList::new(),
-        iter,
-        pred,
-        maxsplit,
-        keep_separator,
-        splited: 0,
-        iter_finished: false
-    };
-
-    ret.ret_buf.push_back(SplitAtOutputItem {
-        is_sep: false,
-        items: Vec::new(),
-        error: None,
-        finished: false
-    });
-
-    return Box::new(ret);
+    let keep_separator = if keep_separator { KeepSeparator::Own } else { KeepSeparator::Drop };
+    let splitter = Splitter::at(iter, pred).keep_separator(keep_separator);
+    match usize::try_from(maxsplit) {
+        Ok(maxsplit) => splitter.maxsplit(maxsplit).split_vec(),
+        Err(_) => splitter.split_vec()
+    }
 }
 
 #[cfg(test)]
 mod tests {
+    use crate::error;
     use crate::utils::{extract_value_from_result_vec, generate_okok_iterator, generate_okokerr_iterator};
 
     use super::*;
@@ -1,6 +1,7 @@
 use crate::error::Error;
-use crate::others::cache_until::cache_until;
+use crate::grouping::splitter::{KeepSeparator, Splitter};
 
+/// Same as `Splitter::at` with `KeepSeparator::AttachToNext`; a negative `maxsplit` means no limit.
 pub fn split_before<T>(iter: Box<dyn Iterator<Item = Result<T,Error>>>, 
     pred: fn(&T) -> Result<bool, Error>,
     maxsplit: i128
@@ -8,13 +9,18 @@ pub fn split_before<T>(iter: Box<dyn Iterator<Item = Result<T,Error>>>,
 where 
 T: Clone + 'static
 {
-    cache_until(iter, pred, maxsplit, false)
+    let splitter = Splitter::at(iter, pred).with_name("split_before").keep_separator(KeepSeparator::AttachToNext);
+    match usize::try_from(maxsplit) {
+        Ok(maxsplit) => splitter.maxsplit(maxsplit).split_vec(),
+        Err(_) => splitter.split_vec()
+    }
 }
 
 #[cfg(test)]
 mod tests {
 
-    use crate::utils::generate_okok_iterator;
+    use crate::error;
+    use crate::utils::{extract_value_from_result_vec, generate_okok_iterator};
 
     use super::*;
 
@@ -59,4 +65,11 @@ mod tests {
         assert_eq!(None, r.next());
         assert_eq!(None, r.next());
     }
-}
\ No newline at end of file
+
+    #[test]
+    fn test2_error() {
+        let r = split_before(generate_okok_iterator(vec![1, 2, 3]), |x| if *x == 2 { Err(error::value_error("bad".to_string())) } else { Ok(false) }, -1);
+        let ret = extract_value_from_result_vec(r.collect::<Vec<_>>());
+        assert_eq!("[split_before] bad", ret.1.unwrap().message().unwrap());
+    }
+}
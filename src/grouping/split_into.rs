@@ -1,97 +1,25 @@
-use std::collections::LinkedList;
-
 use crate::error::Error;
+use crate::grouping::splitter::Splitter;
 
-struct SplitIntoOutputItem<T> {
-    items: Vec<T>,
-    size: usize,
-    finished: bool
-}
-
-pub struct SplitInto<T>
-{
-    ret_buf: LinkedList<SplitIntoOutputItem<T>>,
-    iter: Box<dyn Iterator<Item = Result<T,Error>>>,
-    sizes: Vec<usize>,
-    iter_finished: bool
-}
-
-impl<T> Iterator for SplitInto<T>
+/// Same as `Splitter::sizes`: the input is not read past the last size, and
+/// a piece cut short by an upstream error is emitted before the error.
+pub fn split_into<T>(iter: Box<dyn Iterator<Item = Result<T,Error>>>, sizes: Vec<usize>) -> Box<dyn Iterator<Item = Result<Vec<T>,Error>>>
+where
+T: 'static
 {
-    type Item = Result<Vec<T>, Error>;
-
-    fn next(&mut self) -> Option<Self::Item> {
-        loop {
-            if self.iter_finished {
-                if self.ret_buf.len() > 0 {
-                    let front = self.ret_buf.pop_front();
-                    return Some(Ok(front.unwrap().items));
-                } else {
-                    return None;
-                }
-            }
-
-            if self.ret_buf.len() > 0 {
-                let front = self.ret_buf.front_mut().unwrap();
-                if front.items.len() == front.size {
-                    front.finished = true;
-                }
-
-                if front.finished {
-                    let front = self.ret_buf.pop_front();
-                    return Some(Ok(front.unwrap().items));
-                }
-            }
-
-            let _next = self.iter.next();
-            if let Some(v) = _next {
-                match v {
-                    Ok(ok_v) => {
-                        if self.ret_buf.len() == 0 {
-                            continue;
-                        }
-
-                        let front = self.ret_buf.front_mut();
-                        front.unwrap().items.push(ok_v);
-
-                        continue;
-                    },
-                    Err(err_v) => { // upstream error
-                        self.iter_finished = true;
-                        return Some(Err(err_v));
-                    }
-                }
-            } else {
-                self.iter_finished = true;
-                for item in self.ret_buf.iter_mut() {
-                    item.finished = true;
-                }
-                continue;
-            }
-        }
-    }
+    Splitter::sizes(iter, sizes).split_vec()
 }
 
-pub fn split_into<T>(iter: Box<dyn Iterator<Item = Result<T,Error>>>, sizes: Vec<usize>) -> Box<dyn Iterator<Item = Result<Vec<T>,Error>>>
+/// https://more-itertools.readthedocs.io/en/v10.2.0/api.html#more_itertools.split_into
+///
+/// Same as `Splitter::sizes_from`: `sizes` may be infinite and a `None` size
+/// takes the rest of the input. With `strict`, a `Kind::ValueError` follows
+/// the pieces if the input is longer or shorter than the sizes.
+pub fn split_into_sizes<T>(iter: Box<dyn Iterator<Item = Result<T,Error>>>, sizes: Box<dyn Iterator<Item = Option<usize>>>, strict: bool) -> Box<dyn Iterator<Item = Result<Vec<T>,Error>>>
 where
 T: 'static
 {
-    let mut ret = SplitInto {
-        ret_buf: LinkedList::new(),
-        iter,
-        sizes: sizes.clone(),
-        iter_finished: false
-    };
-
-    for size in ret.sizes.iter() {
-        ret.ret_buf.push_back(SplitIntoOutputItem {
-            finished: false,
-            size: *size,
-            items: Vec::new()
-        });
-    }
-
-    return Box::new(ret);
+    Splitter::sizes_from(iter, sizes).strict(strict).split_vec()
 }
 
 
@@ -130,6 +58,10 @@ mod tests {
         let ret = si.collect::<Vec<_>>();
         let ret2 = extract_value_from_result_vec(ret);
         assert_eq!(vec![vec![1], vec![2, 3], vec![], vec![4], vec![]], ret2.0);
+
+        // an infinite input is not read past the sizes
+        let si = split_into(Box::new((1..).map(Ok)), vec![1, 2]);
+        assert_eq!(vec![vec![1], vec![2, 3]], extract_value_from_result_vec(si.collect::<Vec<_>>()).0);
     }
 
     #[test]
@@ -140,15 +72,28 @@ mod tests {
         let ret = si.collect::<Vec<_>>();
         let ret2 = extract_value_from_result_vec(ret);
         assert_eq!(vec![vec![1], vec![2, 3], vec![4, 5, 6]], ret2.0);
-        assert_eq!(error::Kind::OverflowError, ret2.1.unwrap().kind());
+        assert!(ret2.1.is_none());
 
         let v = vec![1,2,3,4,5,6];
         let sizes = vec![1,2,4];
         let si = split_into(generate_okokerr_iterator(v, error::overflow_error("[test]".to_string())), sizes);
         let ret = si.collect::<Vec<_>>();
         let ret2 = extract_value_from_result_vec(ret);
-        assert_eq!(vec![vec![1], vec![2, 3]], ret2.0);
+        assert_eq!(vec![vec![1], vec![2, 3], vec![4, 5, 6]], ret2.0);
         assert_eq!(error::Kind::OverflowError, ret2.1.unwrap().kind());
     }
 
-}
+    #[test]
+    fn test2_sizes() {
+        let si = split_into_sizes(generate_okok_iterator(vec![1,2,3,4,5,6,7]), Box::new(vec![Some(2), None].into_iter()), false);
+        assert_eq!(vec![vec![1, 2], vec![3, 4, 5, 6, 7]], extract_value_from_result_vec(si.collect::<Vec<_>>()).0);
+
+        let si = split_into_sizes(generate_okok_iterator(vec![1,2,3,4,5,6,7]), Box::new((1..).map(Some)), false);
+        assert_eq!(vec![vec![1], vec![2, 3], vec![4, 5, 6], vec![7]], extract_value_from_result_vec(si.collect::<Vec<_>>()).0);
+
+        let si = split_into_sizes(generate_okok_iterator(vec![1,2,3,4,5,6,7]), Box::new((1..).map(Some)), true);
+        let ret = extract_value_from_result_vec(si.collect::<Vec<_>>());
+        assert_eq!(vec![vec![1], vec![2, 3], vec![4, 5, 6], vec![7]], ret.0);
+        assert_eq!(error::Kind::ValueError, ret.1.unwrap().kind());
+    }
+}
\ No newline at end of file
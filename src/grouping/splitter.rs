@@ -0,0 +1,682 @@
+use std::cell::RefCell;
+use std::collections::{BTreeMap, VecDeque};
+use std::rc::Rc;
+
+use crate::error::{self, Error};
+
+/// Where a separator matched by `Splitter::at` goes.
+#[derive(Clone, Copy, Debug, PartialEq)]
+pub enum KeepSeparator {
+    /// The separator is discarded.
+    Drop,
+    /// The separator is a piece of its own.
+    Own,
+    /// The separator ends the piece before it.
+    AttachToPrevious,
+    /// The separator starts the piece after it.
+    AttachToNext
+}
+
+enum Rule<T> {
+    At(fn(&T) -> Result<bool,Error>),
+    When(fn(&T, &T) -> Result<bool,Error>),
+    Sizes
+}
+
+#[derive(Clone, Copy, PartialEq)]
+enum Mark {
+    Plain,
+    Separator,
+    // a split between this item and the previous one
+    CutBefore
+}
+
+enum Token<T> {
+    // starts the next piece
+    Open,
+    Item(T)
+}
+
+struct Tokenizer<T> {
+    iter: Box<dyn Iterator<Item = Result<T,Error>>>,
+    rule: Rule<T>,
+    name: &'static str,
+    sizes: Box<dyn Iterator<Item = Option<usize>>>,
+    pad_sizes: bool,
+    strict: bool,
+    maxsplit: Option<usize>,
+    from_end: bool,
+    keep_separator: KeepSeparator,
+    tokens: VecDeque<Token<T>>,
+    splits: usize,
+    // split_when: the last item read, waiting for its successor
+    last: Option<(T, Mark)>,
+    // rsplit: the items from the oldest split that may still be among the last `maxsplit` ones
+    held: VecDeque<(T, Mark)>,
+    held_splits: usize,
+    // sizes: items left in the open piece, None for the rest of the input
+    size_left: Option<usize>,
+    size_open: bool,
+    input_ended: bool,
+    error: Option<Error>,
+    iter_finished: bool
+}
+
+impl<T> Tokenizer<T> {
+    fn next_token(&mut self) -> Option<Token<T>> {
+        loop {
+            if let Some(token) = self.tokens.pop_front() {
+                return Some(token);
+            }
+            if self.iter_finished {
+                return None;
+            }
+            match self.rule {
+                Rule::Sizes => self.fill_sizes(),
+                _ => self.fill_pred()
+            }
+        }
+    }
+
+    fn fail(&mut self, err: Error) {
+        self.error = Some(err);
+        self.finish();
+    }
+
+    fn finish(&mut self) {
+        self.iter_finished = true;
+        if let Some((v, mark)) = self.last.take() {
+            self.push_marked(v, mark);
+        }
+        while let Some((v, mark)) = self.held.pop_front() {
+            self.release(v, mark);
+        }
+        self.held_splits = 0;
+    }
+
+    fn fill_pred(&mut self) {
+        let v = match self.iter.next() {
+            Some(Ok(v)) => v,
+            Some(Err(err)) => { // upstream error
+                self.fail(err);
+                return;
+            },
+            None => {
+                self.finish();
+                return;
+            }
+        };
+
+        let pred_ret = match self.rule {
+            Rule::At(pred) => pred(&v).map(|ret| (v, if ret { Mark::Separator } else { Mark::Plain })),
+            Rule::When(pred) => match self.last.take() {
+                None => Ok((v, Mark::Plain)),
+                Some((prev, prev_mark)) => {
+                    let ret = pred(&prev, &v).map(|ret| (v, if ret { Mark::CutBefore } else { Mark::Plain }));
+                    self.push_marked(prev, prev_mark);
+                    ret
+                }
+            },
+            Rule::Sizes => unreachable!()
+        };
+
+        match pred_ret {
+            Ok((v, mark)) => {
+                if let Rule::When(_) = self.rule {
+                    self.last = Some((v, mark));
+                } else {
+                    self.push_marked(v, mark);
+                }
+            },
+            Err(err) => {
+                let err = error::any_error(err.kind(), format!("[{}] {}", self.name, err.message().map_or("", |m| m.as_str())));
+                self.fail(err);
+            }
+        }
+    }
+
+    // Applies `maxsplit` to a marked item.
+    fn push_marked(&mut self, v: T, mark: Mark) {
+        if mark == Mark::Plain {
+            if self.held.is_empty() {
+                self.release(v, mark);
+            } else {
+                self.held.push_back((v, mark));
+            }
+            return;
+        }
+
+        match self.maxsplit {
+            None => self.release(v, mark),
+            Some(maxsplit) if !self.from_end => {
+                if self.splits < maxsplit {
+                    self.splits += 1;
+                    self.release(v, mark);
+                } else {
+                    self.release(v, Mark::Plain);
+                }
+            },
+            Some(0) => self.release(v, Mark::Plain),
+            Some(maxsplit) => {
+                if self.held_splits == maxsplit {
+                    // the oldest held split is no longer among the last ones
+                    let (first, _) = self.held.pop_front().unwrap();
+                    self.release(first, Mark::Plain);
+                    while self.held.front().is_some_and(|(_, m)| *m == Mark::Plain) {
+                        let (item, _) = self.held.pop_front().unwrap();
+                        self.release(item, Mark::Plain);
+                    }
+                    self.held_splits -= 1;
+                }
+                self.held.push_back((v, mark));
+                self.held_splits += 1;
+            }
+        }
+    }
+
+    fn release(&mut self, v: T, mark: Mark) {
+        match mark {
+            Mark::Plain => {
+                self.tokens.push_back(Token::Item(v));
+            },
+            Mark::CutBefore => {
+                self.tokens.push_back(Token::Open);
+                self.tokens.push_back(Token::Item(v));
+            },
+            Mark::Separator => match self.keep_separator {
+                KeepSeparator::Drop => {
+                    self.tokens.push_back(Token::Open);
+                },
+                KeepSeparator::Own => {
+                    self.tokens.push_back(Token::Open);
+                    self.tokens.push_back(Token::Item(v));
+                    self.tokens.push_back(Token::Open);
+                },
+                KeepSeparator::AttachToPrevious => {
+                    self.tokens.push_back(Token::Item(v));
+                    self.tokens.push_back(Token::Open);
+                },
+                KeepSeparator::AttachToNext => {
+                    self.tokens.push_back(Token::Open);
+                    self.tokens.push_back(Token::Item(v));
+                }
+            }
+        }
+    }
+
+    // A piece of positive size is only started once an item for it has been
+    // read, and no item is read once the sizes are used up, except in strict
+    // mode to check that the input ends there.
+    fn fill_sizes(&mut self) {
+        if self.input_ended {
+            match self.sizes.next() {
+                Some(size) => self.end_size(size),
+                None => self.finish()
+            }
+            return;
+        }
+
+        if self.size_open && self.size_left != Some(0) {
+            match self.iter.next() {
+                Some(Ok(v)) => {
+                    if let Some(left) = self.size_left.as_mut() {
+                        *left -= 1;
+                    }
+                    self.tokens.push_back(Token::Item(v));
+                },
+                Some(Err(err)) => { // upstream error
+                    self.fail(err);
+                },
+                None => {
+                    self.input_ended = true;
+                    if self.size_left.is_none() {
+                        self.finish();
+                    } else if self.strict {
+                        self.fail(error::value_error(format!("[{}] the input is shorter than the sizes", self.name)));
+                    }
+                }
+            }
+            return;
+        }
+
+        match self.sizes.next() {
+            Some(Some(0)) => {
+                self.tokens.push_back(Token::Open);
+                self.size_left = Some(0);
+                self.size_open = true;
+            },
+            Some(size) => match self.iter.next() {
+                Some(Ok(v)) => {
+                    self.tokens.push_back(Token::Open);
+                    self.tokens.push_back(Token::Item(v));
+                    self.size_left = size.map(|n| n - 1);
+                    self.size_open = true;
+                },
+                Some(Err(err)) => { // upstream error
+                    self.fail(err);
+                },
+                None => {
+                    self.input_ended = true;
+                    self.end_size(size);
+                }
+            },
+            None if self.strict => match self.iter.next() {
+                Some(Ok(_)) => {
+                    self.fail(error::value_error(format!("[{}] the input is longer than the sizes", self.name)));
+                },
+                Some(Err(err)) => { // upstream error
+                    self.fail(err);
+                },
+                None => {
+                    self.finish();
+                }
+            },
+            None => {
+                self.finish();
+            }
+        }
+    }
+
+    // A size left once the input is exhausted gives an empty piece if it is
+    // 0 or the rest; a positive one ends the pieces unless padding, and in
+    // strict mode is an error.
+    fn end_size(&mut self, size: Option<usize>) {
+        match size {
+            Some(n) if n > 0 && self.strict => {
+                self.fail(error::value_error(format!("[{}] the input is shorter than the sizes", self.name)));
+            },
+            Some(n) if n > 0 && !self.pad_sizes => {
+                self.finish();
+            },
+            Some(_) => {
+                self.tokens.push_back(Token::Open);
+            },
+            None => {
+                self.tokens.push_back(Token::Open);
+                self.finish();
+            }
+        }
+    }
+}
+
+struct SplitInner<T> {
+    tokens: Tokenizer<T>,
+    // number of pieces started; the tokens being read belong to the last one
+    opened: usize,
+    // unread items of the live pieces behind the current one
+    buffers: BTreeMap<usize, VecDeque<T>>
+}
+
+impl<T> SplitInner<T> {
+    fn step(&mut self, index: usize) -> Option<T> {
+        if let Some(v) = self.buffers.get_mut(&index).and_then(|buf| buf.pop_front()) {
+            return Some(v);
+        }
+        if index + 1 < self.opened {
+            return None;
+        }
+
+        match self.tokens.next_token() {
+            Some(Token::Item(v)) => Some(v),
+            Some(Token::Open) => {
+                self.opened += 1;
+                None
+            },
+            None => None
+        }
+    }
+
+    // Reads up to the start of piece `index`, buffering the items of the
+    // pieces before it; false if the input ends first.
+    fn advance_to(&mut self, index: usize) -> bool {
+        while self.opened <= index {
+            match self.tokens.next_token() {
+                Some(Token::Item(v)) => {
+                    if let Some(buf) = self.buffers.get_mut(&(self.opened - 1)) {
+                        buf.push_back(v);
+                    }
+                },
+                Some(Token::Open) => {
+                    self.opened += 1;
+                },
+                None => {
+                    return false;
+                }
+            }
+        }
+        true
+    }
+}
+
+/// One piece of a split, read lazily from the shared input.
+///
+/// When the outer iterator moves on before the piece is consumed, its
+/// remaining items are buffered; dropping the piece discards them.
+pub struct Piece<T> {
+    inner: Rc<RefCell<SplitInner<T>>>,
+    index: usize
+}
+
+impl<T> Iterator for Piece<T> {
+    type Item = T;
+
+    fn next(&mut self) -> Option<Self::Item> {
+        self.inner.borrow_mut().step(self.index)
+    }
+}
+
+impl<T> Drop for Piece<T> {
+    fn drop(&mut self) {
+        if let Ok(mut inner) = self.inner.try_borrow_mut() {
+            inner.buffers.remove(&self.index);
+        }
+    }
+}
+
+/// The pieces of a `Splitter`. An upstream or predicate error ends the
+/// current piece and is then returned by this iterator.
+pub struct Split<T> {
+    inner: Rc<RefCell<SplitInner<T>>>,
+    index: usize,
+    finished: bool
+}
+
+impl<T> Iterator for Split<T> {
+    type Item = Result<Piece<T>,Error>;
+
+    fn next(&mut self) -> Option<Self::Item> {
+        if self.finished {
+            return None;
+        }
+
+        let mut inner = self.inner.borrow_mut();
+        if !inner.advance_to(self.index) {
+            self.finished = true;
+            return inner.tokens.error.take().map(Err);
+        }
+
+        let index = self.index;
+        self.index += 1;
+        inner.buffers.insert(index, VecDeque::new());
+        Some(Ok(Piece {
+            inner: Rc::clone(&self.inner),
+            index
+        }))
+    }
+}
+
+/// Splits an iterator into pieces at separators (`at`), between items
+/// (`when`) or by length (`sizes`).
+///
+/// `maxsplit`, `rsplit` and `keep_separator` do not apply to `sizes`, and
+/// `strict` applies to nothing else.
+pub struct Splitter<T> {
+    iter: Box<dyn Iterator<Item = Result<T,Error>>>,
+    rule: Rule<T>,
+    name: &'static str,
+    sizes: Box<dyn Iterator<Item = Option<usize>>>,
+    pad_sizes: bool,
+    strict: bool,
+    maxsplit: Option<usize>,
+    from_end: bool,
+    keep_separator: KeepSeparator
+}
+
+impl<T> Splitter<T>
+where T: 'static
+{
+    fn new(iter: Box<dyn Iterator<Item = Result<T,Error>>>, rule: Rule<T>, name: &'static str) -> Self {
+        Splitter {
+            iter,
+            rule,
+            name,
+            sizes: Box::new(std::iter::empty()),
+            pad_sizes: false,
+            strict: false,
+            maxsplit: None,
+            from_end: false,
+            keep_separator: KeepSeparator::Drop
+        }
+    }
+
+    /// Splits at the items for which `pred` is true; they are dropped unless
+    /// `keep_separator` says otherwise.
+    pub fn at(iter: Box<dyn Iterator<Item = Result<T,Error>>>, pred: fn(&T) -> Result<bool,Error>) -> Self {
+        Splitter::new(iter, Rule::At(pred), "split_at")
+    }
+
+    /// Splits between two consecutive items when `pred(previous, next)` is true.
+    pub fn when(iter: Box<dyn Iterator<Item = Result<T,Error>>>, pred: fn(&T, &T) -> Result<bool,Error>) -> Self {
+        Splitter::new(iter, Rule::When(pred), "split_when")
+    }
+
+    /// One piece per size, shorter or empty once the input is exhausted. The
+    /// input is not read past the last size.
+    pub fn sizes(iter: Box<dyn Iterator<Item = Result<T,Error>>>, sizes: Vec<usize>) -> Self {
+        let mut ret = Splitter::sizes_from(iter, Box::new(sizes.into_iter().map(Some)));
+        ret.pad_sizes = true;
+        ret
+    }
+
+    /// One piece per size read from `sizes`, which may be infinite; `None`
+    /// takes the rest of the input. Unlike `sizes`, no piece of positive size
+    /// is started once the input is exhausted.
+    pub fn sizes_from(iter: Box<dyn Iterator<Item = Result<T,Error>>>, sizes: Box<dyn Iterator<Item = Option<usize>>>) -> Self {
+        let mut ret = Splitter::new(iter, Rule::Sizes, "split_into");
+        ret.sizes = sizes;
+        ret
+    }
+
+    /// With sizes, a `Kind::ValueError` follows the pieces when the input
+    /// does not have exactly the items they need.
+    pub fn strict(mut self, strict: bool) -> Self {
+        self.strict = strict;
+        self
+    }
+
+    /// The function named in the prefix of its errors.
+    pub(crate) fn with_name(mut self, name: &'static str) -> Self {
+        self.name = name;
+        self
+    }
+
+    /// Splits at most `maxsplit` times, starting from the beginning.
+    pub fn maxsplit(mut self, maxsplit: usize) -> Self {
+        self.maxsplit = Some(maxsplit);
+        self.from_end = false;
+        self
+    }
+
+    /// Splits at most `maxsplit` times, starting from the end. The first piece
+    /// is still read lazily, but up to `maxsplit` splits are buffered until
+    /// the input ends.
+    pub fn rsplit(mut self, maxsplit: usize) -> Self {
+        self.maxsplit = Some(maxsplit);
+        self.from_end = true;
+        self
+    }
+
+    pub fn keep_separator(mut self, keep_separator: KeepSeparator) -> Self {
+        self.keep_separator = keep_separator;
+        self
+    }
+
+    pub fn split(self) -> Split<T> {
+        let mut tokens = VecDeque::new();
+        if !matches!(self.rule, Rule::Sizes) {
+            tokens.push_back(Token::Open);
+        }
+
+        let tokenizer = Tokenizer {
+            iter: self.iter,
+            rule: self.rule,
+            name: self.name,
+            sizes: self.sizes,
+            pad_sizes: self.pad_sizes,
+            strict: self.strict,
+            maxsplit: self.maxsplit,
+            from_end: self.from_end,
+            keep_separator: self.keep_separator,
+            tokens,
+            splits: 0,
+            last: None,
+            held: VecDeque::new(),
+            held_splits: 0,
+            size_left: None,
+            size_open: false,
+            input_ended: false,
+            error: None,
+            iter_finished: false
+        };
+
+        Split {
+            inner: Rc::new(RefCell::new(SplitInner {
+                tokens: tokenizer,
+                opened: 0,
+                buffers: BTreeMap::new()
+            })),
+            index: 0,
+            finished: false
+        }
+    }
+
+    /// Like `split`, with each piece collected into a `Vec`.
+    pub fn split_vec(self) -> Box<dyn Iterator<Item = Result<Vec<T>,Error>>> {
+        Box::new(self.split().map(|piece| piece.map(|p| p.collect())))
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use crate::utils::{extract_value_from_result_vec, generate_okok_iterator, generate_okokerr_iterator};
+
+    use super::*;
+
+    fn pieces<T: 'static>(splitter: Splitter<T>) -> Vec<Vec<T>> {
+        extract_value_from_result_vec(splitter.split_vec().collect::<Vec<_>>()).0
+    }
+
+    #[test]
+    fn test1_keep_separator() {
+        let v = vec![1, 0, 2, 3, 0, 4];
+        let is_sep: fn(&i32) -> Result<bool,Error> = |x| Ok(*x == 0);
+
+        assert_eq!(vec![vec![1], vec![2, 3], vec![4]], pieces(Splitter::at(generate_okok_iterator(v.clone()), is_sep)));
+        assert_eq!(vec![vec![1], vec![0], vec![2, 3], vec![0], vec![4]],
+            pieces(Splitter::at(generate_okok_iterator(v.clone()), is_sep).keep_separator(KeepSeparator::Own)));
+        assert_eq!(vec![vec![1, 0], vec![2, 3, 0], vec![4]],
+            pieces(Splitter::at(generate_okok_iterator(v.clone()), is_sep).keep_separator(KeepSeparator::AttachToPrevious)));
+        assert_eq!(vec![vec![1], vec![0, 2, 3], vec![0, 4]],
+            pieces(Splitter::at(generate_okok_iterator(v), is_sep).keep_separator(KeepSeparator::AttachToNext)));
+
+        assert_eq!(vec![Vec::<i32>::new()], pieces(Splitter::at(generate_okok_iterator(vec![]), is_sep)));
+    }
+
+    #[test]
+    fn test2_maxsplit() {
+        let v = vec![1, 0, 2, 0, 3, 0, 4];
+        let is_sep: fn(&i32) -> Result<bool,Error> = |x| Ok(*x == 0);
+
+        assert_eq!(vec![vec![1], vec![2], vec![3, 0, 4]], pieces(Splitter::at(generate_okok_iterator(v.clone()), is_sep).maxsplit(2)));
+        assert_eq!(vec![vec![1, 0, 2], vec![3], vec![4]], pieces(Splitter::at(generate_okok_iterator(v.clone()), is_sep).rsplit(2)));
+        assert_eq!(vec![vec![1, 0, 2, 0, 3, 0], vec![4]],
+            pieces(Splitter::at(generate_okok_iterator(v.clone()), is_sep).rsplit(1).keep_separator(KeepSeparator::AttachToPrevious)));
+        assert_eq!(vec![v.clone()], pieces(Splitter::at(generate_okok_iterator(v.clone()), is_sep).rsplit(0)));
+        assert_eq!(vec![vec![1], vec![2], vec![3], vec![4]], pieces(Splitter::at(generate_okok_iterator(v), is_sep).rsplit(5)));
+
+        let v = vec![1, 2, 3, 3, 2, 5, 2, 4, 2];
+        assert_eq!(vec![vec![1, 2, 3, 3, 2], vec![5, 2], vec![4, 2]],
+            pieces(Splitter::when(generate_okok_iterator(v), |x, y| Ok(x < y)).rsplit(2)));
+    }
+
+    #[test]
+    fn test3_sizes() {
+        assert_eq!(vec![vec![1], vec![2, 3], vec![4], vec![]], pieces(Splitter::sizes(generate_okok_iterator(vec![1, 2, 3, 4]), vec![1, 2, 3, 4])));
+        assert_eq!(vec![vec![1], vec![], vec![2, 3]], pieces(Splitter::sizes(generate_okok_iterator(vec![1, 2, 3, 4]), vec![1, 0, 2])));
+        assert_eq!(0, pieces(Splitter::sizes(generate_okok_iterator(vec![1, 2]), vec![])).len());
+        assert_eq!(vec![Vec::<i32>::new(), vec![]], pieces(Splitter::sizes(generate_okok_iterator(vec![]), vec![1, 2])));
+
+        // nothing is read once the sizes are used up
+        let ret = Splitter::sizes(generate_okokerr_iterator(vec![1, 2, 3], error::overflow_error("[test]".to_string())), vec![1, 2]).split_vec();
+        let ret = extract_value_from_result_vec(ret.collect::<Vec<_>>());
+        assert_eq!(vec![vec![1], vec![2, 3]], ret.0);
+        assert!(ret.1.is_none());
+
+        let sizes = Box::new(std::iter::repeat(Some(2)));
+        assert_eq!(vec![vec![1, 2], vec![3, 4], vec![5]], pieces(Splitter::sizes_from(generate_okok_iterator(vec![1, 2, 3, 4, 5]), sizes)));
+
+        let sizes = Box::new(vec![Some(1), Some(0), None, Some(2)].into_iter());
+        assert_eq!(vec![vec![1], vec![], vec![2, 3, 4]], pieces(Splitter::sizes_from(generate_okok_iterator(vec![1, 2, 3, 4]), sizes)));
+
+        // a 0 or rest size still gives an empty piece after the input ends
+        let sizes = Box::new(vec![Some(1), Some(2), Some(0)].into_iter());
+        assert_eq!(vec![vec![1], vec![2, 3], vec![]], pieces(Splitter::sizes_from(generate_okok_iterator(vec![1, 2, 3]), sizes)));
+        let sizes = Box::new(vec![Some(2), None].into_iter());
+        assert_eq!(vec![vec![1, 2], vec![]], pieces(Splitter::sizes_from(generate_okok_iterator(vec![1, 2]), sizes)));
+        let sizes = Box::new(vec![None].into_iter());
+        assert_eq!(vec![Vec::<i32>::new()], pieces(Splitter::sizes_from(generate_okok_iterator(vec![]), sizes)));
+        let sizes = Box::new(vec![Some(2), Some(1), Some(0)].into_iter());
+        assert_eq!(vec![vec![1, 2]], pieces(Splitter::sizes_from(generate_okok_iterator(vec![1, 2]), sizes)));
+    }
+
+    #[test]
+    fn test4_lazy_pieces() {
+        let mut split = Splitter::at(generate_okok_iterator(vec![1, 2, 0, 3, 4, 0, 5]), |x| Ok(*x == 0)).split();
+        let mut p1 = split.next().unwrap().ok().unwrap();
+        assert_eq!(Some(1), p1.next());
+
+        let p2 = split.next().unwrap().ok().unwrap();
+        let p3 = split.next().unwrap().ok().unwrap();
+        assert!(split.next().is_none());
+
+        assert_eq!(vec![5], p3.collect::<Vec<_>>());
+        assert_eq!(vec![2], p1.collect::<Vec<_>>());
+        assert_eq!(vec![3, 4], p2.collect::<Vec<_>>());
+
+        // the pieces can be skipped without being read
+        let split = Splitter::when(generate_okok_iterator(vec![1, 2, 2, 3]), |x, y| Ok(x != y)).split();
+        assert_eq!(3, split.count());
+    }
+
+    #[test]
+    fn test5_error() {
+        let mut split = Splitter::at(generate_okokerr_iterator(vec![1, 0, 2], error::overflow_error("[test]".to_string())), |x| Ok(*x == 0)).split();
+        assert_eq!(vec![1], split.next().unwrap().ok().unwrap().collect::<Vec<_>>());
+        assert_eq!(vec![2], split.next().unwrap().ok().unwrap().collect::<Vec<_>>());
+        assert_eq!(error::Kind::OverflowError, split.next().unwrap().err().unwrap().kind());
+        assert!(split.next().is_none());
+
+        let ret = Splitter::when(generate_okok_iterator(vec![1, 2, 3]), |_, y| if *y == 3 { Err(error::value_error("bad".to_string())) } else { Ok(false) }).split_vec();
+        let ret = extract_value_from_result_vec(ret.collect::<Vec<_>>());
+        assert_eq!(vec![vec![1, 2]], ret.0);
+        assert_eq!("[split_when] bad", ret.1.unwrap().message().unwrap());
+    }
+
+    #[test]
+    fn test6_sizes_strict() {
+        let split = |v: Vec<i32>, sizes: Vec<Option<usize>>| {
+            let ret = Splitter::sizes_from(generate_okok_iterator(v), Box::new(sizes.into_iter())).strict(true).split_vec();
+            extract_value_from_result_vec(ret.collect::<Vec<_>>())
+        };
+
+        let ret = split(vec![1, 2, 3], vec![Some(1), Some(2), Some(0)]);
+        assert_eq!(vec![vec![1], vec![2, 3], vec![]], ret.0);
+        assert!(ret.1.is_none());
+
+        let ret = split(vec![1, 2, 3], vec![Some(1), None]);
+        assert_eq!(vec![vec![1], vec![2, 3]], ret.0);
+        assert!(ret.1.is_none());
+
+        let ret = split(vec![1, 2, 3], vec![Some(1), Some(1)]);
+        assert_eq!(vec![vec![1], vec![2]], ret.0);
+        assert_eq!("[split_into] the input is longer than the sizes", ret.1.unwrap().message().unwrap());
+
+        let ret = split(vec![1, 2, 3], vec![Some(2), Some(2)]);
+        assert_eq!(vec![vec![1, 2], vec![3]], ret.0);
+        assert_eq!("[split_into] the input is shorter than the sizes", ret.1.unwrap().message().unwrap());
+
+        let ret = split(vec![1, 2], vec![Some(2), Some(1)]);
+        assert_eq!(vec![vec![1, 2]], ret.0);
+        assert_eq!(error::Kind::ValueError, ret.1.unwrap().kind());
+    }
+}
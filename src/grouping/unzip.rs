@@ -1,6 +1,149 @@
+use std::cell::RefCell;
+use std::collections::VecDeque;
+use std::rc::Rc;
+
 use crate::error::Error;
 
+/// A tuple that `unzip` can split into one iterator per position.
+///
+/// Implemented for tuples of up to 12 elements of any types.
+pub trait UnzipTuple: Sized {
+    const ARITY: usize;
+
+    /// One queue per position, e.g. `(VecDeque<T0>, VecDeque<T1>)`.
+    type Buffers: Default;
+    /// One iterator per position.
+    type Outputs;
+
+    /// Queues every element whose output is still alive.
+    fn push(self, buffers: &mut Self::Buffers, alive: &[bool]);
+    fn outputs(inner: Rc<RefCell<UnzipInner<Self>>>) -> Self::Outputs;
+}
+
+pub struct UnzipInner<S>
+where S: UnzipTuple
+{
+    iter: Box<dyn Iterator<Item = Result<S,Error>>>,
+    buffers: S::Buffers,
+    // outputs not dropped yet; the elements of dropped ones are discarded
+    alive: Vec<bool>,
+    iter_finished: bool,
+    iter_error: Option<Error>
+}
+
+struct UnzipCursor<S, T>
+where S: UnzipTuple
+{
+    no: usize,
+    inner: Rc<RefCell<UnzipInner<S>>>,
+    pop: fn(&mut S::Buffers) -> Option<T>,
+    iter_finished: bool
+}
+
+impl<S, T> Iterator for UnzipCursor<S, T>
+where S: UnzipTuple
+{
+    type Item = Result<T,Error>;
+
+    fn next(&mut self) -> Option<Self::Item> {
+        if self.iter_finished {
+            return None;
+        }
+
+        let mut inner = self.inner.borrow_mut();
+        loop {
+            if let Some(v) = (self.pop)(&mut inner.buffers) {
+                return Some(Ok(v));
+            }
+
+            if let Some(err) = inner.iter_error.as_ref() {
+                self.iter_finished = true;
+                return Some(Err(err.clone()));
+            }
+
+            if inner.iter_finished {
+                self.iter_finished = true;
+                return None;
+            }
+
+            match inner.iter.next() {
+                None => { inner.iter_finished = true; },
+                Some(Err(err)) => { inner.iter_error = Some(err); },
+                Some(Ok(row)) => {
+                    let UnzipInner { buffers, alive, .. } = &mut *inner;
+                    row.push(buffers, alive);
+                }
+            }
+        }
+    }
+}
+
+impl<S, T> Drop for UnzipCursor<S, T>
+where S: UnzipTuple
+{
+    fn drop(&mut self) {
+        let mut inner = self.inner.borrow_mut();
+        inner.alive[self.no] = false;
+        while (self.pop)(&mut inner.buffers).is_some() {}
+    }
+}
+
+macro_rules! impl_unzip_for_tuple {
+    ($count:expr; $($idx:tt $t:ident),+) => {
+        impl<$($t: 'static),+> UnzipTuple for ($($t,)+) {
+            const ARITY: usize = $count;
+
+            type Buffers = ($(VecDeque<$t>,)+);
+            type Outputs = ($(Box<dyn Iterator<Item = Result<$t,Error>>>,)+);
+
+            fn push(self, buffers: &mut Self::Buffers, alive: &[bool]) {
+                $(if alive[$idx] { buffers.$idx.push_back(self.$idx); })+
+            }
+
+            fn outputs(inner: Rc<RefCell<UnzipInner<Self>>>) -> Self::Outputs {
+                ($(
+                    Box::new(UnzipCursor {
+                        no: $idx,
+                        inner: Rc::clone(&inner),
+                        pop: |buffers: &mut Self::Buffers| buffers.$idx.pop_front(),
+                        iter_finished: false
+                    }) as Box<dyn Iterator<Item = Result<$t,Error>>>,
+                )+)
+            }
+        }
+    };
+}
 
+impl_unzip_for_tuple!(1; 0 T0);
+impl_unzip_for_tuple!(2; 0 T0, 1 T1);
+impl_unzip_for_tuple!(3; 0 T0, 1 T1, 2 T2);
+impl_unzip_for_tuple!(4; 0 T0, 1 T1, 2 T2, 3 T3);
+impl_unzip_for_tuple!(5; 0 T0, 1 T1, 2 T2, 3 T3, 4 T4);
+impl_unzip_for_tuple!(6; 0 T0, 1 T1, 2 T2, 3 T3, 4 T4, 5 T5);
+impl_unzip_for_tuple!(7; 0 T0, 1 T1, 2 T2, 3 T3, 4 T4, 5 T5, 6 T6);
+impl_unzip_for_tuple!(8; 0 T0, 1 T1, 2 T2, 3 T3, 4 T4, 5 T5, 6 T6, 7 T7);
+impl_unzip_for_tuple!(9; 0 T0, 1 T1, 2 T2, 3 T3, 4 T4, 5 T5, 6 T6, 7 T7, 8 T8);
+impl_unzip_for_tuple!(10; 0 T0, 1 T1, 2 T2, 3 T3, 4 T4, 5 T5, 6 T6, 7 T7, 8 T8, 9 T9);
+impl_unzip_for_tuple!(11; 0 T0, 1 T1, 2 T2, 3 T3, 4 T4, 5 T5, 6 T6, 7 T7, 8 T8, 9 T9, 10 T10);
+impl_unzip_for_tuple!(12; 0 T0, 1 T1, 2 T2, 3 T3, 4 T4, 5 T5, 6 T6, 7 T7, 8 T8, 9 T9, 10 T10, 11 T11);
+
+/// https://more-itertools.readthedocs.io/en/v10.2.0/api.html#more_itertools.unzip
+///
+/// Lazily splits an iterator of tuples into one iterator per position. Items
+/// are buffered until every live output has read them; an upstream error is
+/// yielded by each output once its buffered items are read.
+pub fn unzip<S>(iter: Box<dyn Iterator<Item = Result<S,Error>>>) -> S::Outputs
+where S: UnzipTuple
+{
+    let inner = Rc::new(RefCell::new(UnzipInner {
+        iter,
+        buffers: S::Buffers::default(),
+        alive: vec![true; S::ARITY],
+        iter_finished: false,
+        iter_error: None
+    }));
+    S::outputs(inner)
+}
 
 pub fn unzip2<T0, T1>(iter: &mut Box<dyn Iterator<Item = Result<(T0,T1),Error>>>) -> Result<(Vec<T0>, Vec<T1>),Error> 
 where T0: 'static, T1: 'static
@@ -111,7 +254,7 @@ pub fn unzip5<T0, T1, T2, T3, T4>(iter: &mut Box<dyn Iterator<Item = Result<(T0,
 
 #[cfg(test)]
 mod tests {
-    use crate::{error, utils::{generate_okok_iterator, generate_okokerr_iterator}};
+    use crate::{error, utils::{extract_value_from_result_vec, generate_okok_iterator, generate_okokerr_iterator}};
 
     use super::*;
 
@@ -186,4 +329,34 @@ mod tests {
         let ret = unzip5(&mut data);
         assert_eq!(error::Kind::OverflowError, ret.as_ref().err().unwrap().kind());
     }
+
+    #[test]
+    fn test3_lazy() {
+        let data = generate_okok_iterator(vec![('a', 1, "aa"), ('b', 2, "bb"), ('c', 3, "cc")]);
+        let (mut t0, t1, t2) = unzip(data);
+        assert_eq!(Some(Ok('a')), t0.next());
+        assert_eq!(vec!["aa", "bb", "cc"], extract_value_from_result_vec(t2.collect::<Vec<_>>()).0);
+        assert_eq!(vec![1, 2, 3], extract_value_from_result_vec(t1.collect::<Vec<_>>()).0);
+        assert_eq!(vec!['b', 'c'], extract_value_from_result_vec(t0.collect::<Vec<_>>()).0);
+    }
+
+    #[test]
+    fn test4_infinite() {
+        let data: Box<dyn Iterator<Item = Result<(usize, usize),Error>>> = Box::new((0..).map(|x| Ok((x, x * x))));
+        let (t0, t1) = unzip(data);
+        drop(t0);
+        assert_eq!(vec![0, 1, 4, 9], extract_value_from_result_vec(t1.take(4).collect::<Vec<_>>()).0);
+    }
+
+    #[test]
+    fn test5_lazy_error() {
+        let data = generate_okokerr_iterator(vec![('a', 1)], error::overflow_error("[test]".to_string()));
+        let (mut t0, mut t1) = unzip(data);
+        assert_eq!(Some(Ok('a')), t0.next());
+        assert_eq!(error::Kind::OverflowError, t0.next().unwrap().err().unwrap().kind());
+        assert_eq!(None, t0.next());
+        assert_eq!(Some(Ok(1)), t1.next());
+        assert_eq!(error::Kind::OverflowError, t1.next().unwrap().err().unwrap().kind());
+        assert_eq!(None, t1.next());
+    }
 }
\ No newline at end of file
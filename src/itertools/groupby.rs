@@ -1,189 +1,346 @@
+use std::cell::RefCell;
 use std::collections::VecDeque;
-use std::fmt::Debug;
-use crate::utils::are_same;
+use std::rc::Rc;
 
-pub struct GroupBy<T> 
-where T: Clone + Debug + PartialEq
-{
-    cur_key: Option<T>,
-    buf: VecDeque<T>,
-    emitted_first: bool,
-    iter: Box<dyn Iterator<Item = T>>,
+use crate::error::Error;
+
+struct GroupInner<T, K> {
+    iter: Box<dyn Iterator<Item = Result<T,Error>>>,
+    key_fn: Box<dyn FnMut(&T) -> K>,
     iter_finished: bool,
-    stop_next: bool,
-}
+    iter_error: Option<Error>,
 
+    current_key: Option<K>,
+    // first element of the group `top_group` when it has been read ahead
+    current_elt: Option<T>,
+    // index of the group the upstream iterator is positioned in
+    top_group: usize,
+    // groups below this index are exhausted
+    oldest_buffered_group: usize,
+    // group index of buffer[0]
+    bottom_group: usize,
+    buffer: Vec<VecDeque<T>>,
+    // highest index of the groups whose Group handle has been dropped
+    dropped_group: Option<usize>,
+}
 
-impl<T> GroupBy<T> 
-where T: Clone + Debug + PartialEq
+impl<T, K> GroupInner<T, K>
+where K: PartialEq
 {
-    pub fn next_group(&mut self) -> Option<T> {
-        loop {
-            if self.iter_finished {
-                return None;
+    fn next_element(&mut self) -> Option<T> {
+        match self.iter.next() {
+            None => {
+                self.iter_finished = true;
+                None
+            },
+            Some(Err(err)) => {
+                self.iter_finished = true;
+                self.iter_error = Some(err);
+                None
+            },
+            Some(Ok(v)) => Some(v)
+        }
+    }
+
+    fn step(&mut self, client: usize) -> Option<T> {
+        if client < self.oldest_buffered_group {
+            None
+        } else if client < self.top_group ||
+                (client == self.top_group && self.buffer.len() > self.top_group - self.bottom_group) {
+            self.lookup_buffer(client)
+        } else if self.iter_finished {
+            None
+        } else if client == self.top_group {
+            self.step_current()
+        } else {
+            self.step_buffering(client)
+        }
+    }
+
+    fn lookup_buffer(&mut self, client: usize) -> Option<T> {
+        let elt = self.buffer.get_mut(client - self.bottom_group).and_then(|queue| queue.pop_front());
+        if elt.is_none() && client == self.oldest_buffered_group {
+            self.oldest_buffered_group += 1;
+            while self.buffer.get(self.oldest_buffered_group - self.bottom_group).is_some_and(|queue| queue.is_empty()) {
+                self.oldest_buffered_group += 1;
+            }
+
+            let nclear = self.oldest_buffered_group - self.bottom_group;
+            if nclear > 0 && nclear >= self.buffer.len() / 2 {
+                self.buffer.drain(0..nclear.min(self.buffer.len()));
+                self.bottom_group = self.oldest_buffered_group;
+            }
+        }
+        elt
+    }
+
+    // The caller asked for a group beyond the current one: read the rest of
+    // the current group into the buffer (unless its handle is gone) and
+    // return the first element of the next group.
+    fn step_buffering(&mut self, client: usize) -> Option<T> {
+        debug_assert!(self.top_group + 1 == client);
+        let keep = !self.is_dropped(self.top_group);
+        let mut group = VecDeque::new();
+
+        if let Some(elt) = self.current_elt.take() {
+            if keep {
+                group.push_back(elt);
+            }
+        }
+
+        let mut first_elt = None;
+        while let Some(elt) = self.next_element() {
+            let key = (self.key_fn)(&elt);
+            let is_new_group = self.current_key.as_ref().is_some_and(|old_key| *old_key != key);
+            self.current_key = Some(key);
+            if is_new_group {
+                first_elt = Some(elt);
+                break;
+            }
+            if keep {
+                group.push_back(elt);
             }
+        }
 
-            let _next;
-            if self.buf.len() > 0 {
-                _next = self.buf.pop_front();
+        if keep {
+            self.push_next_group(group);
+        }
+        if first_elt.is_some() {
+            self.top_group += 1;
+        }
+        first_elt
+    }
+
+    fn push_next_group(&mut self, group: VecDeque<T>) {
+        while self.top_group - self.bottom_group > self.buffer.len() {
+            if self.buffer.is_empty() {
+                self.bottom_group += 1;
+                self.oldest_buffered_group += 1;
             } else {
-                _next = self.iter.next();
+                self.buffer.push(VecDeque::new());
             }
-            
-            match _next {
-                None => {
-                    self.iter_finished = true;
-                    continue;
-                },
-                Some(v) => {
-                    if self.cur_key.is_none() || !are_same(Some(&v), Some(self.cur_key.as_mut().unwrap())) {
-                        self.cur_key = Some(v.clone());
-                        self.emitted_first = false;
-                        self.stop_next = false;
-                        return Some(v);
-                    } else {
-                        continue;
-                    }
-                }
+        }
+        self.buffer.push(group);
+    }
+
+    fn step_current(&mut self) -> Option<T> {
+        if let Some(elt) = self.current_elt.take() {
+            return Some(elt);
+        }
+
+        let elt = self.next_element()?;
+        let key = (self.key_fn)(&elt);
+        let is_new_group = self.current_key.as_ref().is_some_and(|old_key| *old_key != key);
+        self.current_key = Some(key);
+        if is_new_group {
+            self.current_elt = Some(elt);
+            self.top_group += 1;
+            return None;
+        }
+        Some(elt)
+    }
+
+    // Called right after the first element of group `top_group` has been
+    // handed out: peek one element further to learn whether the group ends.
+    fn group_key(&mut self) -> K {
+        let old_key = self.current_key.take().unwrap();
+        if let Some(elt) = self.next_element() {
+            let key = (self.key_fn)(&elt);
+            if old_key != key {
+                self.top_group += 1;
             }
+            self.current_key = Some(key);
+            self.current_elt = Some(elt);
         }
+        old_key
     }
+}
 
-    pub fn collect0(&mut self) -> Vec<T> {
-        return self.collect();
+impl<T, K> GroupInner<T, K> {
+    fn is_dropped(&self, client: usize) -> bool {
+        matches!(self.dropped_group, Some(dropped) if client <= dropped)
+    }
+
+    fn drop_group(&mut self, client: usize) {
+        if !self.is_dropped(client) {
+            self.dropped_group = Some(client);
+        }
     }
 }
 
-impl<T> Iterator for GroupBy<T> 
-where
-T: PartialEq + Clone + Debug
+/// A run of consecutive elements sharing one key.
+///
+/// The elements are read lazily from the shared upstream iterator; when the
+/// outer iterator is advanced before this group is consumed, the remaining
+/// elements are buffered so the group can still be read later.
+pub struct Group<T, K> {
+    inner: Rc<RefCell<GroupInner<T, K>>>,
+    index: usize,
+    first: Option<T>,
+}
+
+impl<T, K> Iterator for Group<T, K>
+where K: PartialEq
 {
     type Item = T;
 
     fn next(&mut self) -> Option<Self::Item> {
-        loop {
-            if self.iter_finished {
-                return None;
-            }
+        if let Some(elt) = self.first.take() {
+            return Some(elt);
+        }
+        self.inner.borrow_mut().step(self.index)
+    }
+}
 
-            if self.stop_next {
-                return None;
-            }
+impl<T, K> Drop for Group<T, K> {
+    fn drop(&mut self) {
+        if let Ok(mut inner) = self.inner.try_borrow_mut() {
+            inner.drop_group(self.index);
+        }
+    }
+}
 
-            if self.cur_key.is_none() {
-                return None;
-            }
+pub struct GroupBy<T, K> {
+    inner: Rc<RefCell<GroupInner<T, K>>>,
+    index: usize,
+    finished: bool,
+}
 
-            if !self.emitted_first {
-                self.emitted_first = true;
-                return Some(self.cur_key.as_ref().unwrap().clone());
-            }
+impl<T, K> Iterator for GroupBy<T, K>
+where K: PartialEq
+{
+    type Item = Result<(K, Group<T, K>), Error>;
 
-            let _next = self.iter.next();
-            match _next {
-                None => {
-                    self.iter_finished = true;
-                    continue;
-                },
-                Some(v) => {
-                    if !are_same(Some(&v), Some(self.cur_key.as_mut().unwrap())) {
-                        self.buf.push_back(v.clone());
-                        self.stop_next = true;
-                        continue;
-                    } else {
-                        return Some(v);
-                    }
-                }
+    fn next(&mut self) -> Option<Self::Item> {
+        if self.finished {
+            return None;
+        }
+
+        let index = self.index;
+        self.index += 1;
+
+        let mut inner = self.inner.borrow_mut();
+        match inner.step(index) {
+            Some(elt) => {
+                let key = inner.group_key();
+                Some(Ok((key, Group {
+                    inner: Rc::clone(&self.inner),
+                    index,
+                    first: Some(elt)
+                })))
+            },
+            None => {
+                self.finished = true;
+                inner.iter_error.take().map(Err)
             }
         }
     }
 }
 
-pub fn groupby<T>(iter: Box<dyn Iterator<Item = T>>) -> GroupBy<T> 
-where
-T: PartialEq + Clone + Debug
+pub(crate) fn groupby_with<T, K>(iter: Box<dyn Iterator<Item = Result<T,Error>>>, key_fn: Box<dyn FnMut(&T) -> K>) -> GroupBy<T, K>
+where K: PartialEq
 {
-    return GroupBy {
-        cur_key: None,
-        buf: VecDeque::new(),
-        emitted_first: false,
+    let inner = GroupInner {
         iter,
+        key_fn,
         iter_finished: false,
-        stop_next: true
+        iter_error: None,
+        current_key: None,
+        current_elt: None,
+        top_group: 0,
+        oldest_buffered_group: 0,
+        bottom_group: 0,
+        buffer: Vec::new(),
+        dropped_group: None,
+    };
+
+    GroupBy {
+        inner: Rc::new(RefCell::new(inner)),
+        index: 0,
+        finished: false
     }
 }
 
+/// https://docs.python.org/3/library/itertools.html#itertools.groupby
+///
+/// An upstream error ends the current group and is then returned by the outer iterator.
+pub fn groupby<T, K>(iter: Box<dyn Iterator<Item = Result<T,Error>>>, key_fn: fn(&T) -> K) -> GroupBy<T, K>
+where
+T: 'static,
+K: PartialEq + 'static
+{
+    groupby_with(iter, Box::new(key_fn))
+}
+
 
 #[cfg(test)]
 mod tests {
-    use crate::itertools::iter::iter_from_vec;
+    use crate::error;
+    use crate::utils::{generate_okok_iterator, generate_okokerr_iterator};
 
     use super::*;
 
     #[test]
     fn test1() {
-        let mut ret = groupby(iter_from_vec("AAAAbbbcccC".chars().collect::<Vec<char>>()));
-        assert_eq!('A', ret.next_group().unwrap());
-        assert_eq!(vec!['A','A','A','A'], ret.collect0());
-        assert_eq!(None, ret.next());
+        let ret = groupby(generate_okok_iterator("AAAAbbbcccC".chars().collect::<Vec<char>>()), |x| *x);
+        let v = ret.map(|x| {
+            let (k, g) = x.ok().unwrap();
+            (k, g.collect::<String>())
+        }).collect::<Vec<_>>();
 
-        assert_eq!('b', ret.next_group().unwrap());
-        assert_eq!(vec!['b','b','b'], ret.collect0());
-        assert_eq!(None, ret.next());
-
-        assert_eq!('c', ret.next_group().unwrap());
-        assert_eq!(vec!['c','c','c'], ret.collect0());
-        assert_eq!(None, ret.next());
-
-        assert_eq!('C', ret.next_group().unwrap());
-        assert_eq!(vec!['C'], ret.collect0());
-        assert_eq!(None, ret.next());
+        assert_eq!(vec![('A', "AAAA".to_string()), ('b', "bbb".to_string()), ('c', "ccc".to_string()), ('C', "C".to_string())], v);
     }
 
     #[test]
-    fn test2() {
-        let mut ret = groupby(iter_from_vec("0AAAAbbbcccCC".chars().collect::<Vec<char>>()));
-        assert_eq!('0', ret.next_group().unwrap());
-        assert_eq!(vec!['0'], ret.collect0());
-        assert_eq!(None, ret.next());
+    fn test2_key() {
+        let ret = groupby(generate_okok_iterator("0AAAAbbbcccCC".chars().collect::<Vec<char>>()), |x| x.to_ascii_lowercase());
+        let v = ret.map(|x| {
+            let (k, g) = x.ok().unwrap();
+            (k, g.collect::<String>())
+        }).collect::<Vec<_>>();
 
-        assert_eq!('A', ret.next_group().unwrap());
-        assert_eq!(vec!['A','A','A','A'], ret.collect0());
-        assert_eq!(None, ret.next());
+        assert_eq!(vec![('0', "0".to_string()), ('a', "AAAA".to_string()), ('b', "bbb".to_string()), ('c', "cccCC".to_string())], v);
+    }
 
-        assert_eq!('b', ret.next_group().unwrap());
-        assert_eq!(vec!['b','b','b'], ret.collect0());
-        assert_eq!(None, ret.next());
+    #[test]
+    fn test3_skip_ahead() {
+        let mut ret = groupby(generate_okok_iterator(vec![1,1,2,2,2,3,4,4]), |x| *x);
+        let (k1, mut g1) = ret.next().unwrap().ok().unwrap();
+        assert_eq!(1, k1);
+        assert_eq!(Some(1), g1.next());
 
-        assert_eq!('c', ret.next_group().unwrap());
-        assert_eq!(vec!['c','c','c'], ret.collect0());
-        assert_eq!(None, ret.next());
+        let (k2, g2) = ret.next().unwrap().ok().unwrap();
+        let (k3, g3) = ret.next().unwrap().ok().unwrap();
+        let (k4, g4) = ret.next().unwrap().ok().unwrap();
+        assert!(ret.next().is_none());
 
-        assert_eq!('C', ret.next_group().unwrap());
-        assert_eq!(vec!['C','C'], ret.collect0());
-        assert_eq!(None, ret.next());
+        assert_eq!((2, 3, 4), (k2, k3, k4));
+        assert_eq!(vec![4,4], g4.collect::<Vec<_>>());
+        assert_eq!(vec![2,2,2], g2.collect::<Vec<_>>());
+        assert_eq!(vec![1], g1.collect::<Vec<_>>());
+        assert_eq!(vec![3], g3.collect::<Vec<_>>());
     }
 
     #[test]
-    fn test3() {
-        let mut ret = groupby(iter_from_vec("0AAAAbbbcccCC".chars().collect::<Vec<char>>()));
-        assert_eq!('0', ret.next_group().unwrap());
-
-        assert_eq!('A', ret.next_group().unwrap());
+    fn test4_dropped_groups() {
+        let ret = groupby(generate_okok_iterator(vec![1,1,2,2,2,3,4,4]), |x| *x);
+        let keys = ret.map(|x| x.ok().unwrap().0).collect::<Vec<_>>();
+        assert_eq!(vec![1,2,3,4], keys);
+    }
 
-        assert_eq!(vec!['A','A','A','A'], ret.collect0());
-        assert_eq!(None, ret.next());
+    #[test]
+    fn test5_error() {
+        let mut ret = groupby(generate_okokerr_iterator(vec![1,1,2], error::overflow_error("[test]".to_string())), |x| *x);
 
-        assert_eq!('b', ret.next_group().unwrap());
-        assert_eq!(vec!['b','b','b'], ret.collect0());
-        assert_eq!(None, ret.next());
+        let (k, g) = ret.next().unwrap().ok().unwrap();
+        assert_eq!(1, k);
+        assert_eq!(vec![1,1], g.collect::<Vec<_>>());
 
-        assert_eq!('c', ret.next_group().unwrap());
-        assert_eq!(vec!['c','c','c'], ret.collect0());
-        assert_eq!(None, ret.next());
+        let (k, g) = ret.next().unwrap().ok().unwrap();
+        assert_eq!(2, k);
+        assert_eq!(vec![2], g.collect::<Vec<_>>());
 
-        assert_eq!('C', ret.next_group().unwrap());
-        assert_eq!(vec!['C','C'], ret.collect0());
-        assert_eq!(None, ret.next());
+        assert_eq!(error::Kind::OverflowError, ret.next().unwrap().err().unwrap().kind());
+        assert!(ret.next().is_none());
     }
-}
\ No newline at end of file
+}
@@ -40,7 +40,8 @@ where
 T: Clone
 {
     no: usize,
-    inner: Rc<RefCell<TeeInner<T>>>
+    inner: Rc<RefCell<TeeInner<T>>>,
+    iter_finished: bool
 }
 
 impl<T> Iterator for TeeCursor<T> 
@@ -49,36 +50,50 @@ T: Clone
 {
     type Item = Result<T, Error>;
 
+    // Reads the upstream only when this cursor has nothing buffered, queuing
+    // a copy for the other cursor; an upstream error is yielded once by each.
     fn next(&mut self) -> Option<Self::Item> {
-        let _next = self.inner.borrow_mut().iter.next();
-        match _next {
+        if self.iter_finished {
+            return None;
+        }
+
+        let mut inner = self.inner.borrow_mut();
+        let front = if self.no == 1 { inner.buf1.pop_front() } else { inner.buf2.pop_front() };
+        if let Some(v) = front {
+            return Some(Ok(v));
+        }
+
+        if let Some(err) = inner.iter_error.as_ref() {
+            self.iter_finished = true;
+            return Some(Err(err.clone()));
+        }
+
+        if inner.iter_finished {
+            self.iter_finished = true;
+            return None;
+        }
+
+        match inner.iter.next() {
             None => {
-                self.inner.borrow_mut().iter_finished = true;
-            }, 
-            Some(v) => {
-                if v.is_ok() {
-                    self.inner.borrow_mut().buf1.push_back(v.as_ref().ok().unwrap().clone());
-                    self.inner.borrow_mut().buf2.push_back(v.as_ref().ok().unwrap().clone());
+                inner.iter_finished = true;
+                self.iter_finished = true;
+                None
+            },
+            Some(Err(err)) => { // upstream error
+                inner.iter_finished = true;
+                inner.iter_error = Some(err.clone());
+                self.iter_finished = true;
+                Some(Err(err))
+            },
+            Some(Ok(v)) => {
+                if self.no == 1 {
+                    inner.buf2.push_back(v.clone());
                 } else {
-                    self.inner.borrow_mut().iter_error = Some(v.err().unwrap());
+                    inner.buf1.push_back(v.clone());
                 }
+                Some(Ok(v))
             }
         }
-        if self.no == 1 {
-            if self.inner.borrow_mut().iter_finished && self.inner.borrow_mut().buf1.len() == 0 {
-                return None;
-            } else {
-                return Some(Ok(self.inner.borrow_mut().buf1.pop_front().unwrap()));
-            }
-        } else if self.no == 2 {
-            if self.inner.borrow_mut().iter_finished && self.inner.borrow_mut().buf2.len() == 0 {
-                return None;
-            } else {
-                return Some(Ok(self.inner.borrow_mut().buf2.pop_front().unwrap()));
-            }
-        } else {
-            return None;
-        }
     }
 }
 
@@ -99,12 +114,14 @@ T: Clone + 'static
     pub fn iter(&self) -> (Box<dyn Iterator<Item=Result<T,Error>>>, Box<dyn Iterator<Item=Result<T,Error>>>) {
         let ret0: Box<dyn Iterator<Item=Result<T,Error>>> = Box::new(TeeCursor {
             no: 1,
-            inner: Rc::clone(&self.inner)
+            inner: Rc::clone(&self.inner),
+            iter_finished: false
         });
 
         let ret1: Box<dyn Iterator<Item=Result<T,Error>>> = Box::new(TeeCursor {
             no: 2,
-            inner: Rc::clone(&self.inner)
+            inner: Rc::clone(&self.inner),
+            iter_finished: false
         });
 
         return (ret0, ret1);
@@ -125,6 +142,7 @@ T: Clone + 'static
 mod tests {
     use std::vec;
 
+    use crate::error;
     use crate::utils::extract_value_from_result_vec;
     use crate::utils::generate_okok_iterator;
 
@@ -186,4 +204,19 @@ mod tests {
         assert_eq!(None, t1.next());
         assert_eq!(None, t2.next());
     }
+
+    #[test]
+    fn test2_error() {
+        let v = vec![Ok(1), Ok(2), Err(error::overflow_error("[test]".to_string()))];
+        let (mut t1, mut t2) = tee(Box::new(v.into_iter()));
+        assert_eq!(Some(1), t1.next().unwrap().ok());
+        assert_eq!(Some(2), t1.next().unwrap().ok());
+        assert_eq!(error::Kind::OverflowError, t1.next().unwrap().err().unwrap().kind());
+        assert_eq!(None, t1.next());
+
+        assert_eq!(Some(1), t2.next().unwrap().ok());
+        assert_eq!(Some(2), t2.next().unwrap().ok());
+        assert_eq!(error::Kind::OverflowError, t2.next().unwrap().err().unwrap().kind());
+        assert_eq!(None, t2.next());
+    }
 }
\ No newline at end of file
@@ -1,3 +1,6 @@
+use std::collections::{HashMap, VecDeque};
+use std::hash::Hash;
+
 use crate::error::Error;
 use crate::error;
 use crate::sequence::Sequence;
@@ -54,9 +57,320 @@ pub fn locate<T>(
 }
 
 
+struct LocateBy<T> {
+    iter: Box<dyn Iterator<Item = Result<T,Error>>>,
+    pred: fn(&T) -> bool,
+    index: usize,
+    iter_finished: bool
+}
+
+impl<T> Iterator for LocateBy<T> {
+    type Item = Result<usize, Error>;
+
+    fn next(&mut self) -> Option<Self::Item> {
+        while !self.iter_finished {
+            match self.iter.next() {
+                None => { self.iter_finished = true; },
+                Some(Err(err)) => {
+                    self.iter_finished = true;
+                    return Some(Err(err));
+                },
+                Some(Ok(v)) => {
+                    let index = self.index;
+                    self.index += 1;
+                    if (self.pred)(&v) {
+                        return Some(Ok(index));
+                    }
+                }
+            }
+        }
+        None
+    }
+}
+
+/// https://more-itertools.readthedocs.io/en/v10.2.0/api.html#more_itertools.locate
+///
+/// Indices of the items for which `pred` is true.
+pub fn locate_by<T>(iter: Box<dyn Iterator<Item = Result<T,Error>>>, pred: fn(&T) -> bool) -> Box<dyn Iterator<Item = Result<usize, Error>>>
+where T: 'static
+{
+    Box::new(LocateBy {
+        iter,
+        pred,
+        index: 0,
+        iter_finished: false
+    })
+}
+
+// Knuth-Morris-Pratt matcher fed one item at a time.
+struct Kmp<T> {
+    pattern: Vec<T>,
+    // fail[j]: length of the longest proper border of pattern[..=j]
+    fail: Vec<usize>,
+    state: usize
+}
+
+impl<T> Kmp<T>
+where T: PartialEq
+{
+    fn new(pattern: Vec<T>) -> Kmp<T> {
+        let mut fail = vec![0; pattern.len()];
+        let mut k = 0;
+        for j in 1..pattern.len() {
+            while k > 0 && pattern[j] != pattern[k] {
+                k = fail[k - 1];
+            }
+            if pattern[j] == pattern[k] {
+                k += 1;
+            }
+            fail[j] = k;
+        }
+        Kmp { pattern, fail, state: 0 }
+    }
+
+    // Whether a match ends with `item`.
+    fn push(&mut self, item: &T) -> bool {
+        while self.state > 0 && *item != self.pattern[self.state] {
+            self.state = self.fail[self.state - 1];
+        }
+        if *item == self.pattern[self.state] {
+            self.state += 1;
+        }
+        if self.state == self.pattern.len() {
+            self.state = self.fail[self.state - 1];
+            return true;
+        }
+        false
+    }
+}
+
+struct AcNode<T> {
+    next: HashMap<T, usize>,
+    fail: usize,
+    // ids of the patterns ending here, including through the fail links
+    out: Vec<usize>
+}
+
+// Aho-Corasick automaton fed one item at a time.
+struct AhoCorasick<T> {
+    nodes: Vec<AcNode<T>>,
+    state: usize
+}
+
+impl<T> AhoCorasick<T>
+where T: Hash + Eq + Clone
+{
+    fn new(patterns: &[Vec<T>]) -> AhoCorasick<T> {
+        let mut nodes = vec![AcNode { next: HashMap::new(), fail: 0, out: Vec::new() }];
+        for (id, pattern) in patterns.iter().enumerate() {
+            let mut v = 0;
+            for item in pattern.iter() {
+                v = match nodes[v].next.get(item) {
+                    Some(u) => *u,
+                    None => {
+                        nodes.push(AcNode { next: HashMap::new(), fail: 0, out: Vec::new() });
+                        let u = nodes.len() - 1;
+                        nodes[v].next.insert(item.clone(), u);
+                        u
+                    }
+                };
+            }
+            nodes[v].out.push(id);
+        }
+
+        let mut queue = nodes[0].next.values().cloned().collect::<VecDeque<_>>();
+        while let Some(v) = queue.pop_front() {
+            let edges = nodes[v].next.iter().map(|(item, u)| (item.clone(), *u)).collect::<Vec<_>>();
+            for (item, u) in edges {
+                let mut f = nodes[v].fail;
+                let fail = loop {
+                    if let Some(w) = nodes[f].next.get(&item) {
+                        break *w;
+                    }
+                    if f == 0 {
+                        break 0;
+                    }
+                    f = nodes[f].fail;
+                };
+                nodes[u].fail = fail;
+                let inherited = nodes[fail].out.clone();
+                nodes[u].out.extend(inherited);
+                queue.push_back(u);
+            }
+        }
+
+        AhoCorasick { nodes, state: 0 }
+    }
+
+    // Ids of the patterns ending with `item`.
+    fn push(&mut self, item: &T) -> &[usize] {
+        loop {
+            if let Some(u) = self.nodes[self.state].next.get(item) {
+                self.state = *u;
+                break;
+            }
+            if self.state == 0 {
+                break;
+            }
+            self.state = self.nodes[self.state].fail;
+        }
+        &self.nodes[self.state].out
+    }
+}
+
+enum Matcher<T> {
+    Single(Kmp<T>),
+    Multi(AhoCorasick<T>)
+}
+
+struct LocatePatterns<T> {
+    iter: Box<dyn Iterator<Item = Result<T,Error>>>,
+    matcher: Matcher<T>,
+    lens: Vec<usize>,
+    pending: VecDeque<(usize, usize)>,
+    index: usize,
+    init_error: Option<Error>,
+    iter_finished: bool
+}
+
+impl<T> Iterator for LocatePatterns<T>
+where T: Hash + Eq + Clone
+{
+    type Item = Result<(usize, usize), Error>;
+
+    fn next(&mut self) -> Option<Self::Item> {
+        if let Some(err) = self.init_error.take() {
+            self.iter_finished = true;
+            return Some(Err(err));
+        }
+
+        loop {
+            if let Some(m) = self.pending.pop_front() {
+                return Some(Ok(m));
+            }
+
+            if self.iter_finished {
+                return None;
+            }
+
+            match self.iter.next() {
+                None => { self.iter_finished = true; },
+                Some(Err(err)) => {
+                    self.iter_finished = true;
+                    return Some(Err(err));
+                },
+                Some(Ok(v)) => {
+                    let end = self.index + 1;
+                    self.index += 1;
+                    match &mut self.matcher {
+                        Matcher::Single(kmp) => {
+                            if kmp.push(&v) {
+                                self.pending.push_back((0, end - self.lens[0]));
+                            }
+                        },
+                        Matcher::Multi(ac) => {
+                            let mut found = ac.push(&v).iter().map(|id| (*id, end - self.lens[*id])).collect::<Vec<_>>();
+                            found.sort_by_key(|(id, start)| (*start, *id));
+                            self.pending.extend(found);
+                        }
+                    }
+                }
+            }
+        }
+    }
+}
+
+/// Finds every occurrence, overlapping ones included, of any of `patterns` in
+/// a stream, without buffering it. Yields `(pattern_id, start_index)` in order
+/// of the match end, then of the start. A single pattern is matched with
+/// Knuth-Morris-Pratt, several with Aho-Corasick.
+pub fn locate_patterns<T>(iter: Box<dyn Iterator<Item = Result<T,Error>>>, patterns: Vec<Vec<T>>) -> Box<dyn Iterator<Item = Result<(usize, usize), Error>>>
+where T: Hash + Eq + Clone + 'static
+{
+    let mut init_error = None;
+    if patterns.is_empty() || patterns.iter().any(|p| p.is_empty()) {
+        init_error = Some(error::value_error("[locate] patterns should not be empty".to_string()));
+    }
+
+    let lens = patterns.iter().map(|p| p.len()).collect::<Vec<_>>();
+    let matcher = if patterns.len() == 1 {
+        Matcher::Single(Kmp::new(patterns.into_iter().next().unwrap()))
+    } else {
+        Matcher::Multi(AhoCorasick::new(&patterns))
+    };
+
+    Box::new(LocatePatterns {
+        iter,
+        matcher,
+        lens,
+        pending: VecDeque::new(),
+        index: 0,
+        init_error,
+        iter_finished: false
+    })
+}
+
+struct LocatePattern<T> {
+    iter: Box<dyn Iterator<Item = Result<T,Error>>>,
+    kmp: Kmp<T>,
+    index: usize,
+    iter_finished: bool
+}
+
+impl<T> Iterator for LocatePattern<T>
+where T: PartialEq
+{
+    type Item = Result<usize, Error>;
+
+    fn next(&mut self) -> Option<Self::Item> {
+        if self.iter_finished {
+            return None;
+        }
+
+        if self.kmp.pattern.is_empty() {
+            self.iter_finished = true;
+            return Some(Err(error::value_error("[locate] pattern should not be empty".to_string())));
+        }
+
+        loop {
+            match self.iter.next() {
+                None => {
+                    self.iter_finished = true;
+                    return None;
+                },
+                Some(Err(err)) => {
+                    self.iter_finished = true;
+                    return Some(Err(err));
+                },
+                Some(Ok(v)) => {
+                    self.index += 1;
+                    if self.kmp.push(&v) {
+                        return Some(Ok(self.index - self.kmp.pattern.len()));
+                    }
+                }
+            }
+        }
+    }
+}
+
+/// Start indices of every occurrence, overlapping ones included, of `pattern`
+/// in a stream; only needs `PartialEq` items.
+pub fn locate_pattern<T>(iter: Box<dyn Iterator<Item = Result<T,Error>>>, pattern: Vec<T>) -> Box<dyn Iterator<Item = Result<usize, Error>>>
+where T: PartialEq + 'static
+{
+    Box::new(LocatePattern {
+        iter,
+        kmp: Kmp::new(pattern),
+        index: 0,
+        iter_finished: false
+    })
+}
+
+
 #[cfg(test)]
 mod tests {
     use crate::sequence::create_seq_from_vec;
+    use crate::utils::{extract_value_from_result_vec, generate_okok_iterator, generate_okokerr_iterator};
 
     use super::*;
 
@@ -84,4 +398,46 @@ mod tests {
         assert_eq!(Some(Ok(9)), l.next());
         assert_eq!(None, l.next());
     }
+
+    #[test]
+    fn test2_by() {
+        let ret = locate_by(generate_okok_iterator(vec![0, 1, 1, 0, 1]), |x| *x == 1);
+        assert_eq!(vec![1, 2, 4], extract_value_from_result_vec(ret.collect::<Vec<_>>()).0);
+    }
+
+    #[test]
+    fn test3_pattern() {
+        let ret = locate_pattern(generate_okok_iterator(vec![1, 1, 1, 1, 1]), vec![1, 1, 1]);
+        assert_eq!(vec![0, 1, 2], extract_value_from_result_vec(ret.collect::<Vec<_>>()).0);
+
+        let ret = locate_pattern(generate_okok_iterator("abababcab".chars().collect()), "abab".chars().collect());
+        assert_eq!(vec![0, 2], extract_value_from_result_vec(ret.collect::<Vec<_>>()).0);
+
+        let ret = locate_patterns(generate_okok_iterator("aabaab".chars().collect()), vec!["aab".chars().collect()]);
+        assert_eq!(vec![(0, 0), (0, 3)], extract_value_from_result_vec(ret.collect::<Vec<_>>()).0);
+    }
+
+    #[test]
+    fn test4_patterns() {
+        let patterns = vec!["he", "she", "his", "hers"].into_iter().map(|p| p.chars().collect()).collect();
+        let ret = locate_patterns(generate_okok_iterator("ushers".chars().collect()), patterns);
+        assert_eq!(vec![(1, 1), (0, 2), (3, 2)], extract_value_from_result_vec(ret.collect::<Vec<_>>()).0);
+
+        let ret = locate_patterns(generate_okok_iterator(vec![1, 2, 1, 2, 1]), vec![vec![1, 2, 1], vec![2], vec![1]]);
+        assert_eq!(vec![(2, 0), (1, 1), (0, 0), (2, 2), (1, 3), (0, 2), (2, 4)],
+            extract_value_from_result_vec(ret.collect::<Vec<_>>()).0);
+    }
+
+    #[test]
+    fn test5_error() {
+        let mut ret = locate_patterns(generate_okok_iterator(vec![1, 2]), vec![vec![1], vec![]]);
+        assert_eq!(error::Kind::ValueError, ret.next().unwrap().err().unwrap().kind());
+        assert_eq!(None, ret.next());
+
+        let mut ret = locate_pattern(generate_okokerr_iterator(vec![1, 2, 1], error::overflow_error("[test]".to_string())), vec![1]);
+        assert_eq!(Some(Ok(0)), ret.next());
+        assert_eq!(Some(Ok(2)), ret.next());
+        assert_eq!(error::Kind::OverflowError, ret.next().unwrap().err().unwrap().kind());
+        assert_eq!(None, ret.next());
+    }
 }
\ No newline at end of file
@@ -192,10 +192,106 @@ where T: Clone + PartialEq + 'static
 }
 
 
+struct ReplaceBy<T> {
+    iter: Box<dyn Iterator<Item = Result<T,Error>>>,
+    pred: fn(&[T]) -> bool,
+    substitutes: fn(&[T]) -> Vec<T>,
+    count: Option<usize>,
+    window_size: usize,
+    replaced: usize,
+    window: VecDeque<T>,
+    output: VecDeque<T>,
+    error: Option<Error>,
+    iter_finished: bool
+}
+
+impl<T> ReplaceBy<T> {
+    fn can_replace(&self) -> bool {
+        self.count.map_or(true, |count| self.replaced < count)
+    }
+}
+
+impl<T> Iterator for ReplaceBy<T> {
+    type Item = Result<T,Error>;
+
+    fn next(&mut self) -> Option<Self::Item> {
+        loop {
+            if let Some(v) = self.output.pop_front() {
+                return Some(Ok(v));
+            }
+
+            if let Some(err) = self.error.take() {
+                return Some(Err(err));
+            }
+
+            if self.iter_finished && self.window.is_empty() {
+                return None;
+            }
+
+            while !self.iter_finished && self.window.len() < self.window_size {
+                match self.iter.next() {
+                    Some(Ok(v)) => { self.window.push_back(v); },
+                    Some(Err(err)) => { // upstream error, after the pending items
+                        self.iter_finished = true;
+                        self.output.extend(self.window.drain(..));
+                        self.error = Some(err);
+                    },
+                    None => { self.iter_finished = true; }
+                }
+            }
+
+            if self.window.len() == self.window_size && self.can_replace() && (self.pred)(self.window.make_contiguous()) {
+                self.replaced += 1;
+                self.output.extend((self.substitutes)(self.window.make_contiguous()));
+                self.window.clear();
+                continue;
+            }
+
+            if let Some(v) = self.window.pop_front() {
+                return Some(Ok(v));
+            }
+        }
+    }
+}
+
+/// https://more-itertools.readthedocs.io/en/v10.2.0/api.html#more_itertools.replace
+///
+/// Replaces each window of `window_size` consecutive items for which `pred`
+/// is true by `substitutes(window)`, at most `count` times. Windows do not
+/// overlap a replaced one.
+pub fn replace_by<T>(iter: Box<dyn Iterator<Item = Result<T,Error>>>,
+                     pred: fn(&[T]) -> bool,
+                     substitutes: fn(&[T]) -> Vec<T>,
+                     count: Option<usize>,
+                     window_size: usize) -> Box<dyn Iterator<Item = Result<T,Error>>>
+where T: 'static
+{
+    let mut error = None;
+    let mut iter_finished = false;
+    if window_size == 0 {
+        error = Some(error::value_error("[replace:window_size should gt 0]".to_string()));
+        iter_finished = true;
+    }
+
+    Box::new(ReplaceBy {
+        iter,
+        pred,
+        substitutes,
+        count,
+        window_size,
+        replaced: 0,
+        window: VecDeque::with_capacity(window_size),
+        output: VecDeque::new(),
+        error,
+        iter_finished
+    })
+}
+
+
 #[cfg(test)]
 mod tests {
 
-    use crate::utils::{generate_okok_iterator, generate_okokerr_iterator};
+    use crate::utils::{extract_value_from_result_vec, generate_okok_iterator, generate_okokerr_iterator};
 
     use super::*;
 
@@ -255,4 +351,35 @@ mod tests {
         assert_eq!(error::Kind::OverflowError, r.next().unwrap().err().unwrap().kind());
         assert_eq!(None, r.next());
     }
+
+    #[test]
+    fn test2_by() {
+        let r = replace_by(generate_okok_iterator(vec![1, 1, 0, 1, 1, 0, 1, 1]), |w| w[0] == 0, |_| vec![2, 3], None, 1);
+        assert_eq!(vec![1, 1, 2, 3, 1, 1, 2, 3, 1, 1], extract_value_from_result_vec(r.collect::<Vec<_>>()).0);
+
+        let r = replace_by(generate_okok_iterator(vec![1, 1, 0, 1, 1, 0, 1, 1, 0]), |w| w[0] == 0, |_| vec![2, 3], Some(2), 1);
+        assert_eq!(vec![1, 1, 2, 3, 1, 1, 2, 3, 1, 1, 0], extract_value_from_result_vec(r.collect::<Vec<_>>()).0);
+    }
+
+    #[test]
+    fn test3_by_window() {
+        let r = replace_by(generate_okok_iterator(vec![0, 1, 2, 5, 0, 1, 2, 5]), |w| w == [0, 1, 2], |_| vec![3, 4], None, 3);
+        assert_eq!(vec![3, 4, 5, 3, 4, 5], extract_value_from_result_vec(r.collect::<Vec<_>>()).0);
+
+        // the substitutes depend on the matched window, which does not overlap the next one
+        let r = replace_by(generate_okok_iterator(vec![1, 1, 1, 1, 1]), |w| w[0] == w[1], |w| vec![w[0] + w[1]], None, 2);
+        assert_eq!(vec![2, 2, 1], extract_value_from_result_vec(r.collect::<Vec<_>>()).0);
+    }
+
+    #[test]
+    fn test4_by_error() {
+        let mut r = replace_by(generate_okok_iterator(vec![1]), |_| true, |_| vec![], None, 0);
+        assert_eq!(error::Kind::ValueError, r.next().unwrap().err().unwrap().kind());
+        assert_eq!(None, r.next());
+
+        let r = replace_by(generate_okokerr_iterator(vec![1, 2, 1, 2, 1], error::overflow_error("[test]".to_string())), |w| w == [1, 2], |_| vec![0], None, 2);
+        let ret = extract_value_from_result_vec(r.collect::<Vec<_>>());
+        assert_eq!(vec![0, 0, 1], ret.0);
+        assert_eq!(error::Kind::OverflowError, ret.1.unwrap().kind());
+    }
 }
\ No newline at end of file
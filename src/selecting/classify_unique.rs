@@ -0,0 +1,107 @@
+use std::collections::HashSet;
+use std::hash::Hash;
+
+use crate::error::Error;
+use crate::utils::SeenSet;
+
+struct ClassifyUnique<T, K, S> {
+    iter: Box<dyn Iterator<Item = Result<T,Error>>>,
+    key: fn(&T) -> K,
+    seen: S,
+    last_key: Option<K>,
+    iter_finished: bool
+}
+
+impl<T, K, S> Iterator for ClassifyUnique<T, K, S>
+where
+K: PartialEq,
+S: SeenSet<K>
+{
+    type Item = Result<(T, bool, bool),Error>;
+
+    fn next(&mut self) -> Option<Self::Item> {
+        if self.iter_finished {
+            return None;
+        }
+
+        match self.iter.next() {
+            None => {
+                self.iter_finished = true;
+                None
+            },
+            Some(Err(err)) => {
+                self.iter_finished = true;
+                Some(Err(err))
+            },
+            Some(Ok(v)) => {
+                let k = (self.key)(&v);
+                let unique_justseen = self.last_key.as_ref() != Some(&k);
+                let unique_everseen = !self.seen.contains_key(&k);
+                if unique_everseen {
+                    self.seen.insert_key((self.key)(&v));
+                }
+                self.last_key = Some(k);
+                Some(Ok((v, unique_justseen, unique_everseen)))
+            }
+        }
+    }
+}
+
+/// https://more-itertools.readthedocs.io/en/v10.2.0/api.html#more_itertools.classify_unique
+///
+/// Yields `(item, is_unique_just_seen, is_unique_ever_seen)`.
+pub fn classify_unique<T, K>(iter: Box<dyn Iterator<Item = Result<T,Error>>>, key: fn(&T) -> K) -> Box<dyn Iterator<Item = Result<(T, bool, bool),Error>>>
+where
+T: 'static,
+K: Hash + Eq + 'static
+{
+    classify_unique_with(iter, key, HashSet::new())
+}
+
+/// `classify_unique` remembering keys in `seen`, e.g. a `BTreeSet` or a
+/// `SeenVec` for keys that are not hashable.
+pub fn classify_unique_with<T, K, S>(iter: Box<dyn Iterator<Item = Result<T,Error>>>, key: fn(&T) -> K, seen: S) -> Box<dyn Iterator<Item = Result<(T, bool, bool),Error>>>
+where
+T: 'static,
+K: PartialEq + 'static,
+S: SeenSet<K> + 'static
+{
+    Box::new(ClassifyUnique {
+        iter,
+        key,
+        seen,
+        last_key: None,
+        iter_finished: false
+    })
+}
+
+#[cfg(test)]
+mod tests {
+    use crate::error;
+    use crate::utils::{extract_value_from_result_vec, generate_okok_iterator, generate_okokerr_iterator, SeenVec};
+
+    use super::*;
+
+    #[test]
+    fn test1() {
+        let ret = classify_unique(generate_okok_iterator("otto".chars().collect::<Vec<_>>()), |x| *x);
+        assert_eq!(vec![('o', true, true), ('t', true, true), ('t', false, false), ('o', true, false)],
+            extract_value_from_result_vec(ret.collect::<Vec<_>>()).0);
+    }
+
+    #[test]
+    fn test2_unhashable() {
+        let v = vec![vec![1.0], vec![1.0], vec![2.0], vec![1.0]];
+        let ret = classify_unique_with(generate_okok_iterator(v), |x| x.clone(), SeenVec::new());
+        assert_eq!(vec![(vec![1.0], true, true), (vec![1.0], false, false), (vec![2.0], true, true), (vec![1.0], true, false)],
+            extract_value_from_result_vec(ret.collect::<Vec<_>>()).0);
+    }
+
+    #[test]
+    fn test3_error() {
+        let mut ret = classify_unique(generate_okokerr_iterator(vec![1], error::overflow_error("[test]".to_string())), |x| *x);
+        assert_eq!(Some(Ok((1, true, true))), ret.next());
+        assert_eq!(error::Kind::OverflowError, ret.next().unwrap().err().unwrap().kind());
+        assert_eq!(None, ret.next());
+    }
+}
@@ -0,0 +1,99 @@
+use std::collections::HashSet;
+use std::hash::Hash;
+
+use crate::error::Error;
+use crate::utils::SeenSet;
+
+struct DuplicatesEverseen<T, K, S> {
+    iter: Box<dyn Iterator<Item = Result<T,Error>>>,
+    key: fn(&T) -> K,
+    seen: S,
+    iter_finished: bool
+}
+
+impl<T, K, S> Iterator for DuplicatesEverseen<T, K, S>
+where S: SeenSet<K>
+{
+    type Item = Result<T,Error>;
+
+    fn next(&mut self) -> Option<Self::Item> {
+        if self.iter_finished {
+            return None;
+        }
+
+        loop {
+            match self.iter.next() {
+                None => {
+                    self.iter_finished = true;
+                    return None;
+                },
+                Some(Err(err)) => {
+                    self.iter_finished = true;
+                    return Some(Err(err));
+                },
+                Some(Ok(v)) => {
+                    if !self.seen.insert_key((self.key)(&v)) {
+                        return Some(Ok(v));
+                    }
+                }
+            }
+        }
+    }
+}
+
+/// https://more-itertools.readthedocs.io/en/v10.2.0/api.html#more_itertools.duplicates_everseen
+pub fn duplicates_everseen<T, K>(iter: Box<dyn Iterator<Item = Result<T,Error>>>, key: fn(&T) -> K) -> Box<dyn Iterator<Item = Result<T,Error>>>
+where
+T: 'static,
+K: Hash + Eq + 'static
+{
+    duplicates_everseen_with(iter, key, HashSet::new())
+}
+
+/// `duplicates_everseen` remembering keys in `seen`, e.g. a `BTreeSet` or a
+/// `SeenVec` for keys that are not hashable.
+pub fn duplicates_everseen_with<T, K, S>(iter: Box<dyn Iterator<Item = Result<T,Error>>>, key: fn(&T) -> K, seen: S) -> Box<dyn Iterator<Item = Result<T,Error>>>
+where
+T: 'static,
+K: 'static,
+S: SeenSet<K> + 'static
+{
+    Box::new(DuplicatesEverseen {
+        iter,
+        key,
+        seen,
+        iter_finished: false
+    })
+}
+
+#[cfg(test)]
+mod tests {
+    use crate::error;
+    use crate::utils::{extract_value_from_result_vec, generate_okok_iterator, generate_okokerr_iterator, SeenVec};
+
+    use super::*;
+
+    #[test]
+    fn test1() {
+        let ret = duplicates_everseen(generate_okok_iterator("mississippi".chars().collect::<Vec<_>>()), |x| *x);
+        assert_eq!("sissipi".chars().collect::<Vec<_>>(), extract_value_from_result_vec(ret.collect::<Vec<_>>()).0);
+
+        let ret = duplicates_everseen(generate_okok_iterator("AaaBbbCccAaa".chars().collect::<Vec<_>>()), |x| x.to_ascii_lowercase());
+        assert_eq!("aabbccAaa".chars().collect::<Vec<_>>(), extract_value_from_result_vec(ret.collect::<Vec<_>>()).0);
+    }
+
+    #[test]
+    fn test2_unhashable() {
+        let v = vec![vec![1.0], vec![2.0], vec![1.0], vec![1.0]];
+        let ret = duplicates_everseen_with(generate_okok_iterator(v), |x| x.clone(), SeenVec::new());
+        assert_eq!(vec![vec![1.0], vec![1.0]], extract_value_from_result_vec(ret.collect::<Vec<_>>()).0);
+    }
+
+    #[test]
+    fn test3_error() {
+        let mut ret = duplicates_everseen(generate_okokerr_iterator(vec![1,1,2], error::overflow_error("[test]".to_string())), |x| *x);
+        assert_eq!(Some(Ok(1)), ret.next());
+        assert_eq!(error::Kind::OverflowError, ret.next().unwrap().err().unwrap().kind());
+        assert_eq!(None, ret.next());
+    }
+}
@@ -10,4 +10,10 @@ pub mod map_except;
 pub mod nth;
 pub mod before_and_after;
 pub mod take;
-pub mod tail;
\ No newline at end of file
+pub mod tail;
+pub mod unique_everseen;
+pub mod unique_justseen;
+pub mod duplicates_everseen;
+pub mod duplicates_justseen;
+pub mod classify_unique;
+pub mod unique_in_window;
\ No newline at end of file
@@ -0,0 +1,105 @@
+use std::collections::HashSet;
+use std::hash::Hash;
+
+use crate::error::Error;
+use crate::utils::SeenSet;
+
+struct UniqueEverseen<T, K, S> {
+    iter: Box<dyn Iterator<Item = Result<T,Error>>>,
+    key: fn(&T) -> K,
+    seen: S,
+    iter_finished: bool
+}
+
+impl<T, K, S> Iterator for UniqueEverseen<T, K, S>
+where S: SeenSet<K>
+{
+    type Item = Result<T,Error>;
+
+    fn next(&mut self) -> Option<Self::Item> {
+        if self.iter_finished {
+            return None;
+        }
+
+        loop {
+            match self.iter.next() {
+                None => {
+                    self.iter_finished = true;
+                    return None;
+                },
+                Some(Err(err)) => {
+                    self.iter_finished = true;
+                    return Some(Err(err));
+                },
+                Some(Ok(v)) => {
+                    if self.seen.insert_key((self.key)(&v)) {
+                        return Some(Ok(v));
+                    }
+                }
+            }
+        }
+    }
+}
+
+/// https://more-itertools.readthedocs.io/en/v10.2.0/api.html#more_itertools.unique_everseen
+pub fn unique_everseen<T, K>(iter: Box<dyn Iterator<Item = Result<T,Error>>>, key: fn(&T) -> K) -> Box<dyn Iterator<Item = Result<T,Error>>>
+where
+T: 'static,
+K: Hash + Eq + 'static
+{
+    unique_everseen_with(iter, key, HashSet::new())
+}
+
+/// `unique_everseen` remembering keys in `seen`, e.g. a `BTreeSet` or a
+/// `SeenVec` for keys that are not hashable.
+pub fn unique_everseen_with<T, K, S>(iter: Box<dyn Iterator<Item = Result<T,Error>>>, key: fn(&T) -> K, seen: S) -> Box<dyn Iterator<Item = Result<T,Error>>>
+where
+T: 'static,
+K: 'static,
+S: SeenSet<K> + 'static
+{
+    Box::new(UniqueEverseen {
+        iter,
+        key,
+        seen,
+        iter_finished: false
+    })
+}
+
+#[cfg(test)]
+mod tests {
+    use std::collections::BTreeSet;
+
+    use crate::error;
+    use crate::utils::{extract_value_from_result_vec, generate_okok_iterator, generate_okokerr_iterator, SeenVec};
+
+    use super::*;
+
+    #[test]
+    fn test1() {
+        let ret = unique_everseen(generate_okok_iterator("AAAABBBCCDAABBB".chars().collect::<Vec<_>>()), |x| *x);
+        assert_eq!(vec!['A','B','C','D'], extract_value_from_result_vec(ret.collect::<Vec<_>>()).0);
+
+        let ret = unique_everseen(generate_okok_iterator("ABBcCAD".chars().collect::<Vec<_>>()), |x| x.to_ascii_lowercase());
+        assert_eq!(vec!['A','B','c','D'], extract_value_from_result_vec(ret.collect::<Vec<_>>()).0);
+    }
+
+    #[test]
+    fn test2_unhashable() {
+        let v = vec![vec![1.0, 2.0], vec![2.0, 3.0], vec![1.0, 2.0]];
+        let ret = unique_everseen_with(generate_okok_iterator(v), |x| x.clone(), SeenVec::new());
+        assert_eq!(vec![vec![1.0, 2.0], vec![2.0, 3.0]], extract_value_from_result_vec(ret.collect::<Vec<_>>()).0);
+
+        let ret = unique_everseen_with(generate_okok_iterator(vec![3,1,3,2,1]), |x| *x, BTreeSet::new());
+        assert_eq!(vec![3,1,2], extract_value_from_result_vec(ret.collect::<Vec<_>>()).0);
+    }
+
+    #[test]
+    fn test3_error() {
+        let mut ret = unique_everseen(generate_okokerr_iterator(vec![1,1,2], error::overflow_error("[test]".to_string())), |x| *x);
+        assert_eq!(Some(Ok(1)), ret.next());
+        assert_eq!(Some(Ok(2)), ret.next());
+        assert_eq!(error::Kind::OverflowError, ret.next().unwrap().err().unwrap().kind());
+        assert_eq!(None, ret.next());
+    }
+}
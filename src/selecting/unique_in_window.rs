@@ -0,0 +1,182 @@
+use std::collections::{HashMap, VecDeque};
+use std::hash::Hash;
+
+use crate::error::{self, Error};
+
+struct UniqueInWindow<T, K> {
+    iter: Box<dyn Iterator<Item = Result<T,Error>>>,
+    n: usize,
+    key: fn(&T) -> K,
+    window: VecDeque<K>,
+    counts: HashMap<K, usize>,
+    iter_finished: bool
+}
+
+impl<T, K> Iterator for UniqueInWindow<T, K>
+where K: Hash + Eq + Clone
+{
+    type Item = Result<T,Error>;
+
+    fn next(&mut self) -> Option<Self::Item> {
+        if self.iter_finished {
+            return None;
+        }
+
+        if self.n == 0 {
+            self.iter_finished = true;
+            return Some(Err(error::value_error("n must be greater than 0".to_string())));
+        }
+
+        loop {
+            match self.iter.next() {
+                None => {
+                    self.iter_finished = true;
+                    return None;
+                },
+                Some(Err(err)) => {
+                    self.iter_finished = true;
+                    return Some(Err(err));
+                },
+                Some(Ok(v)) => {
+                    if self.window.len() == self.n {
+                        let to_discard = self.window.pop_front().unwrap();
+                        if let Some(count) = self.counts.get_mut(&to_discard) {
+                            *count -= 1;
+                            if *count == 0 {
+                                self.counts.remove(&to_discard);
+                            }
+                        }
+                    }
+
+                    let k = (self.key)(&v);
+                    let is_unique = !self.counts.contains_key(&k);
+                    *self.counts.entry(k.clone()).or_insert(0) += 1;
+                    self.window.push_back(k);
+                    if is_unique {
+                        return Some(Ok(v));
+                    }
+                }
+            }
+        }
+    }
+}
+
+struct UniqueInWindowEq<T, K> {
+    iter: Box<dyn Iterator<Item = Result<T,Error>>>,
+    n: usize,
+    key: fn(&T) -> K,
+    window: VecDeque<K>,
+    iter_finished: bool
+}
+
+impl<T, K> Iterator for UniqueInWindowEq<T, K>
+where K: PartialEq
+{
+    type Item = Result<T,Error>;
+
+    fn next(&mut self) -> Option<Self::Item> {
+        if self.iter_finished {
+            return None;
+        }
+
+        if self.n == 0 {
+            self.iter_finished = true;
+            return Some(Err(error::value_error("n must be greater than 0".to_string())));
+        }
+
+        loop {
+            match self.iter.next() {
+                None => {
+                    self.iter_finished = true;
+                    return None;
+                },
+                Some(Err(err)) => {
+                    self.iter_finished = true;
+                    return Some(Err(err));
+                },
+                Some(Ok(v)) => {
+                    if self.window.len() == self.n {
+                        self.window.pop_front();
+                    }
+
+                    let k = (self.key)(&v);
+                    let is_unique = !self.window.contains(&k);
+                    self.window.push_back(k);
+                    if is_unique {
+                        return Some(Ok(v));
+                    }
+                }
+            }
+        }
+    }
+}
+
+/// https://more-itertools.readthedocs.io/en/v10.2.0/api.html#more_itertools.unique_in_window
+pub fn unique_in_window<T, K>(iter: Box<dyn Iterator<Item = Result<T,Error>>>, n: usize, key: fn(&T) -> K) -> Box<dyn Iterator<Item = Result<T,Error>>>
+where
+T: 'static,
+K: Hash + Eq + Clone + 'static
+{
+    Box::new(UniqueInWindow {
+        iter,
+        n,
+        key,
+        window: VecDeque::new(),
+        counts: HashMap::new(),
+        iter_finished: false
+    })
+}
+
+/// `unique_in_window` for keys that are only `PartialEq`; each step scans the window.
+pub fn unique_in_window_eq<T, K>(iter: Box<dyn Iterator<Item = Result<T,Error>>>, n: usize, key: fn(&T) -> K) -> Box<dyn Iterator<Item = Result<T,Error>>>
+where
+T: 'static,
+K: PartialEq + 'static
+{
+    Box::new(UniqueInWindowEq {
+        iter,
+        n,
+        key,
+        window: VecDeque::new(),
+        iter_finished: false
+    })
+}
+
+#[cfg(test)]
+mod tests {
+    use crate::utils::{extract_value_from_result_vec, generate_okok_iterator, generate_okokerr_iterator};
+
+    use super::*;
+
+    #[test]
+    fn test1() {
+        let v = vec![0, 1, 0, 2, 3, 0];
+        let ret = unique_in_window(generate_okok_iterator(v.clone()), 3, |x| *x);
+        assert_eq!(vec![0, 1, 2, 3, 0], extract_value_from_result_vec(ret.collect::<Vec<_>>()).0);
+
+        let ret = unique_in_window_eq(generate_okok_iterator(v), 3, |x| *x);
+        assert_eq!(vec![0, 1, 2, 3, 0], extract_value_from_result_vec(ret.collect::<Vec<_>>()).0);
+    }
+
+    #[test]
+    fn test2_key() {
+        let v = "abAcBa".chars().collect::<Vec<_>>();
+        let ret = unique_in_window(generate_okok_iterator(v.clone()), 2, |x| x.to_ascii_lowercase());
+        assert_eq!("abAcBa".chars().collect::<Vec<_>>(), extract_value_from_result_vec(ret.collect::<Vec<_>>()).0);
+
+        let ret = unique_in_window_eq(generate_okok_iterator(v), 3, |x| x.to_ascii_lowercase());
+        assert_eq!("abcBa".chars().collect::<Vec<_>>(), extract_value_from_result_vec(ret.collect::<Vec<_>>()).0);
+    }
+
+    #[test]
+    fn test3_error() {
+        let mut ret = unique_in_window(generate_okok_iterator(vec![1]), 0, |x| *x);
+        assert_eq!(error::Kind::ValueError, ret.next().unwrap().err().unwrap().kind());
+        assert_eq!(None, ret.next());
+
+        let mut ret = unique_in_window_eq(generate_okokerr_iterator(vec![1, 1], error::overflow_error("[test]".to_string())), 2, |x| *x);
+        assert_eq!(Some(Ok(1)), ret.next());
+        assert_eq!(error::Kind::OverflowError, ret.next().unwrap().err().unwrap().kind());
+        assert_eq!(None, ret.next());
+    }
+}
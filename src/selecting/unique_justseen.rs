@@ -0,0 +1,81 @@
+use crate::error::Error;
+
+struct UniqueJustseen<T, K> {
+    iter: Box<dyn Iterator<Item = Result<T,Error>>>,
+    key: fn(&T) -> K,
+    last_key: Option<K>,
+    iter_finished: bool
+}
+
+impl<T, K> Iterator for UniqueJustseen<T, K>
+where K: PartialEq
+{
+    type Item = Result<T,Error>;
+
+    fn next(&mut self) -> Option<Self::Item> {
+        if self.iter_finished {
+            return None;
+        }
+
+        loop {
+            match self.iter.next() {
+                None => {
+                    self.iter_finished = true;
+                    return None;
+                },
+                Some(Err(err)) => {
+                    self.iter_finished = true;
+                    return Some(Err(err));
+                },
+                Some(Ok(v)) => {
+                    let k = (self.key)(&v);
+                    let is_new = self.last_key.as_ref() != Some(&k);
+                    self.last_key = Some(k);
+                    if is_new {
+                        return Some(Ok(v));
+                    }
+                }
+            }
+        }
+    }
+}
+
+/// https://more-itertools.readthedocs.io/en/v10.2.0/api.html#more_itertools.unique_justseen
+pub fn unique_justseen<T, K>(iter: Box<dyn Iterator<Item = Result<T,Error>>>, key: fn(&T) -> K) -> Box<dyn Iterator<Item = Result<T,Error>>>
+where
+T: 'static,
+K: PartialEq + 'static
+{
+    Box::new(UniqueJustseen {
+        iter,
+        key,
+        last_key: None,
+        iter_finished: false
+    })
+}
+
+#[cfg(test)]
+mod tests {
+    use crate::error;
+    use crate::utils::{extract_value_from_result_vec, generate_okok_iterator, generate_okokerr_iterator};
+
+    use super::*;
+
+    #[test]
+    fn test1() {
+        let ret = unique_justseen(generate_okok_iterator("AAAABBBCCDAABBB".chars().collect::<Vec<_>>()), |x| *x);
+        assert_eq!("ABCDAB".chars().collect::<Vec<_>>(), extract_value_from_result_vec(ret.collect::<Vec<_>>()).0);
+
+        let ret = unique_justseen(generate_okok_iterator("ABBcCAD".chars().collect::<Vec<_>>()), |x| x.to_ascii_lowercase());
+        assert_eq!("ABcAD".chars().collect::<Vec<_>>(), extract_value_from_result_vec(ret.collect::<Vec<_>>()).0);
+    }
+
+    #[test]
+    fn test2_error() {
+        let mut ret = unique_justseen(generate_okokerr_iterator(vec![1,1,2], error::overflow_error("[test]".to_string())), |x| *x);
+        assert_eq!(Some(Ok(1)), ret.next());
+        assert_eq!(Some(Ok(2)), ret.next());
+        assert_eq!(error::Kind::OverflowError, ret.next().unwrap().err().unwrap().kind());
+        assert_eq!(None, ret.next());
+    }
+}
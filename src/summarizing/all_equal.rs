@@ -0,0 +1,43 @@
+use crate::error::Error;
+
+/// https://more-itertools.readthedocs.io/en/v10.2.0/api.html#more_itertools.all_equal
+pub fn all_equal<T, K>(iter: Box<dyn Iterator<Item = Result<T,Error>>>, key: fn(&T) -> K) -> Result<bool, Error>
+where K: PartialEq
+{
+    let mut first: Option<K> = None;
+    for item in iter {
+        let k = key(&item?);
+        match first.as_ref() {
+            None => { first = Some(k); },
+            Some(f) => {
+                if *f != k {
+                    return Ok(false);
+                }
+            }
+        }
+    }
+    Ok(true)
+}
+
+#[cfg(test)]
+mod tests {
+    use crate::error;
+    use crate::utils::{generate_okok_iterator, generate_okokerr_iterator};
+
+    use super::*;
+
+    #[test]
+    fn test1() {
+        assert!(all_equal(generate_okok_iterator("aaaa".chars().collect::<Vec<_>>()), |x| *x).ok().unwrap());
+        assert!(!all_equal(generate_okok_iterator("aaab".chars().collect::<Vec<_>>()), |x| *x).ok().unwrap());
+        assert!(all_equal(generate_okok_iterator("AaaA".chars().collect::<Vec<_>>()), |x| x.to_ascii_lowercase()).ok().unwrap());
+        assert!(all_equal(generate_okok_iterator(Vec::<i32>::new()), |x| *x).ok().unwrap());
+    }
+
+    #[test]
+    fn test2_early_exit() {
+        assert!(!all_equal(generate_okokerr_iterator(vec![1,2], error::overflow_error("[test]".to_string())), |x| *x).ok().unwrap());
+        assert_eq!(error::Kind::OverflowError,
+            all_equal(generate_okokerr_iterator(vec![1,1], error::overflow_error("[test]".to_string())), |x| *x).err().unwrap().kind());
+    }
+}
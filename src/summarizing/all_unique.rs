@@ -0,0 +1,48 @@
+use std::collections::HashSet;
+use std::hash::Hash;
+
+use crate::error::Error;
+use crate::utils::SeenSet;
+
+/// https://more-itertools.readthedocs.io/en/v10.2.0/api.html#more_itertools.all_unique
+pub fn all_unique<T, K>(iter: Box<dyn Iterator<Item = Result<T,Error>>>, key: fn(&T) -> K) -> Result<bool, Error>
+where K: Hash + Eq
+{
+    all_unique_with(iter, key, HashSet::new())
+}
+
+/// `all_unique` remembering keys in `seen`, e.g. a `BTreeSet` or a
+/// `SeenVec` for keys that are not hashable.
+pub fn all_unique_with<T, K, S>(iter: Box<dyn Iterator<Item = Result<T,Error>>>, key: fn(&T) -> K, mut seen: S) -> Result<bool, Error>
+where S: SeenSet<K>
+{
+    for item in iter {
+        if !seen.insert_key(key(&item?)) {
+            return Ok(false);
+        }
+    }
+    Ok(true)
+}
+
+#[cfg(test)]
+mod tests {
+    use crate::error;
+    use crate::utils::{generate_okok_iterator, generate_okokerr_iterator, SeenVec};
+
+    use super::*;
+
+    #[test]
+    fn test1() {
+        assert!(!all_unique(generate_okok_iterator("ABCB".chars().collect::<Vec<_>>()), |x| *x).ok().unwrap());
+        assert!(all_unique(generate_okok_iterator("ABCb".chars().collect::<Vec<_>>()), |x| *x).ok().unwrap());
+        assert!(!all_unique(generate_okok_iterator("ABCb".chars().collect::<Vec<_>>()), |x| x.to_ascii_lowercase()).ok().unwrap());
+        assert!(!all_unique_with(generate_okok_iterator(vec![vec![1.0], vec![1.0]]), |x| x.clone(), SeenVec::new()).ok().unwrap());
+    }
+
+    #[test]
+    fn test2_early_exit() {
+        assert!(!all_unique(generate_okokerr_iterator(vec![1,1], error::overflow_error("[test]".to_string())), |x| *x).ok().unwrap());
+        assert_eq!(error::Kind::OverflowError,
+            all_unique(generate_okokerr_iterator(vec![1,2], error::overflow_error("[test]".to_string())), |x| *x).err().unwrap().kind());
+    }
+}
@@ -0,0 +1,327 @@
+use std::cmp::{Ordering, Reverse};
+use std::collections::{BinaryHeap, HashMap};
+use std::hash::Hash;
+use std::ops::{Add, BitAnd, BitOr, Sub};
+
+use crate::error::{self, Error};
+
+#[derive(Clone, Debug)]
+struct Entry {
+    count: usize,
+    // insertion rank, used to break ties the way Python's Counter does
+    seq: usize
+}
+
+/// A multiset in the spirit of Python's `collections.Counter`.
+///
+/// Counts never go below zero: `subtract` and `-` drop the items whose count
+/// reaches zero. Items with equal counts are reported in first-seen order.
+#[derive(Clone, Debug)]
+pub struct Counter<T>
+where T: Hash + Eq
+{
+    counts: HashMap<T, Entry>,
+    next_seq: usize
+}
+
+// Ordering used by the heap in most_common: higher count first, then earlier seq.
+struct Ranked<'a, T> {
+    item: &'a T,
+    count: usize,
+    seq: usize
+}
+
+impl<T> PartialEq for Ranked<'_, T> {
+    fn eq(&self, other: &Self) -> bool {
+        self.cmp(other) == Ordering::Equal
+    }
+}
+
+impl<T> Eq for Ranked<'_, T> {}
+
+impl<T> PartialOrd for Ranked<'_, T> {
+    fn partial_cmp(&self, other: &Self) -> Option<Ordering> {
+        Some(self.cmp(other))
+    }
+}
+
+impl<T> Ord for Ranked<'_, T> {
+    fn cmp(&self, other: &Self) -> Ordering {
+        self.count.cmp(&other.count).then_with(|| other.seq.cmp(&self.seq))
+    }
+}
+
+impl<T> Counter<T>
+where T: Hash + Eq
+{
+    pub fn new() -> Counter<T> {
+        Counter {
+            counts: HashMap::new(),
+            next_seq: 0
+        }
+    }
+
+    /// Counts the items of a `Result` iterator, stopping at the first error.
+    pub fn from_result_iter(iter: Box<dyn Iterator<Item = Result<T,Error>>>) -> Result<Counter<T>, Error> {
+        let mut ret = Counter::new();
+        ret.update(iter)?;
+        Ok(ret)
+    }
+
+    pub fn update(&mut self, iter: Box<dyn Iterator<Item = Result<T,Error>>>) -> Result<(), Error> {
+        for item in iter {
+            let item = item?;
+            let count = self.get(&item);
+            match count.checked_add(1) {
+                None => { return Err(error::overflow_error("[counter] overflow".to_string())); },
+                Some(count) => { self.set(item, count); }
+            }
+        }
+        Ok(())
+    }
+
+    /// Adds `n` to the count of `item`, saturating at `usize::MAX`.
+    pub fn add_count(&mut self, item: T, n: usize) {
+        let count = self.get(&item).saturating_add(n);
+        self.set(item, count);
+    }
+
+    fn set(&mut self, item: T, count: usize) {
+        if count == 0 {
+            self.counts.remove(&item);
+            return;
+        }
+
+        match self.counts.get_mut(&item) {
+            Some(entry) => { entry.count = count; },
+            None => {
+                self.counts.insert(item, Entry { count, seq: self.next_seq });
+                self.next_seq += 1;
+            }
+        }
+    }
+
+    pub fn get(&self, item: &T) -> usize {
+        self.counts.get(item).map_or(0, |entry| entry.count)
+    }
+
+    /// Number of distinct items.
+    pub fn len(&self) -> usize {
+        self.counts.len()
+    }
+
+    pub fn is_empty(&self) -> bool {
+        self.counts.is_empty()
+    }
+
+    pub fn total(&self) -> Result<usize, Error> {
+        let mut ret: usize = 0;
+        for entry in self.counts.values() {
+            ret = match ret.checked_add(entry.count) {
+                None => { return Err(error::overflow_error("[counter] total overflow".to_string())); },
+                Some(v) => v
+            };
+        }
+        Ok(ret)
+    }
+
+    /// Decreases counts by the ones in `other`, removing items that reach zero.
+    pub fn subtract(&mut self, other: &Counter<T>)
+    where T: Clone
+    {
+        for (item, entry) in other.counts.iter() {
+            let count = self.get(item).saturating_sub(entry.count);
+            self.set(item.clone(), count);
+        }
+    }
+
+    /// The `k` most common items and their counts, most common first
+    /// (all items when `k` is `None`). Uses a heap of size `k`.
+    pub fn most_common(&self, k: Option<usize>) -> Vec<(T, usize)>
+    where T: Clone
+    {
+        let ranked = self.counts.iter().map(|(item, entry)| Ranked {
+            item,
+            count: entry.count,
+            seq: entry.seq
+        });
+
+        let mut top = match k {
+            None => ranked.collect::<Vec<_>>(),
+            Some(k) => {
+                let mut heap = BinaryHeap::with_capacity(k + 1);
+                for r in ranked {
+                    heap.push(Reverse(r));
+                    if heap.len() > k {
+                        heap.pop();
+                    }
+                }
+                heap.into_iter().map(|r| r.0).collect::<Vec<_>>()
+            }
+        };
+
+        top.sort_by(|a, b| b.cmp(a));
+        top.into_iter().map(|r| (r.item.clone(), r.count)).collect()
+    }
+
+    /// Iterates over each item repeated as many times as its count, in first-seen order.
+    pub fn elements(&self) -> Box<dyn Iterator<Item = T>>
+    where T: Clone + 'static
+    {
+        let mut entries = self.counts.iter().map(|(item, entry)| (entry.seq, item.clone(), entry.count)).collect::<Vec<_>>();
+        entries.sort_by_key(|x| x.0);
+        Box::new(entries.into_iter().flat_map(|(_, item, count)| std::iter::repeat(item).take(count)))
+    }
+
+    /// Iterates over `(item, count)` pairs in arbitrary order.
+    pub fn iter(&self) -> impl Iterator<Item = (&T, usize)> {
+        self.counts.iter().map(|(item, entry)| (item, entry.count))
+    }
+
+    fn combine(mut self, other: Counter<T>, op: fn(usize, usize) -> usize) -> Counter<T> {
+        let mut others = other.counts.into_iter().collect::<Vec<_>>();
+        others.sort_by_key(|x| x.1.seq);
+
+        for (item, entry) in others.into_iter() {
+            let count = op(self.get(&item), entry.count);
+            self.set(item, count);
+        }
+        self
+    }
+}
+
+impl<T> Default for Counter<T>
+where T: Hash + Eq
+{
+    fn default() -> Self {
+        Counter::new()
+    }
+}
+
+impl<T> PartialEq for Counter<T>
+where T: Hash + Eq
+{
+    fn eq(&self, other: &Self) -> bool {
+        self.len() == other.len() && self.iter().all(|(item, count)| other.get(item) == count)
+    }
+}
+
+impl<T> FromIterator<T> for Counter<T>
+where T: Hash + Eq
+{
+    fn from_iter<I: IntoIterator<Item = T>>(iter: I) -> Self {
+        let mut ret = Counter::new();
+        ret.extend(iter);
+        ret
+    }
+}
+
+impl<T> Extend<T> for Counter<T>
+where T: Hash + Eq
+{
+    fn extend<I: IntoIterator<Item = T>>(&mut self, iter: I) {
+        for item in iter {
+            self.add_count(item, 1);
+        }
+    }
+}
+
+impl<T> Add for Counter<T>
+where T: Hash + Eq
+{
+    type Output = Counter<T>;
+
+    fn add(self, other: Counter<T>) -> Counter<T> {
+        self.combine(other, usize::saturating_add)
+    }
+}
+
+impl<T> Sub for Counter<T>
+where T: Hash + Eq
+{
+    type Output = Counter<T>;
+
+    fn sub(self, other: Counter<T>) -> Counter<T> {
+        self.combine(other, usize::saturating_sub)
+    }
+}
+
+impl<T> BitOr for Counter<T>
+where T: Hash + Eq
+{
+    type Output = Counter<T>;
+
+    /// Union: the maximum of both counts.
+    fn bitor(self, other: Counter<T>) -> Counter<T> {
+        self.combine(other, usize::max)
+    }
+}
+
+impl<T> BitAnd for Counter<T>
+where T: Hash + Eq
+{
+    type Output = Counter<T>;
+
+    /// Intersection: the minimum of both counts.
+    fn bitand(mut self, other: Counter<T>) -> Counter<T> {
+        self.counts.retain(|item, _| other.counts.contains_key(item));
+        self.combine(other, usize::min)
+    }
+}
+
+
+#[cfg(test)]
+mod tests {
+    use crate::utils::{generate_okok_iterator, generate_okokerr_iterator};
+
+    use super::*;
+
+    #[test]
+    fn test_from_result_iter() {
+        let v = vec![5,1,2,2,3,3,3,4,4,4,4,5,5,5,5];
+        let c = Counter::from_result_iter(generate_okok_iterator(v)).ok().unwrap();
+        assert_eq!(1, c.get(&1));
+        assert_eq!(2, c.get(&2));
+        assert_eq!(3, c.get(&3));
+        assert_eq!(4, c.get(&4));
+        assert_eq!(5, c.get(&5));
+        assert_eq!(0, c.get(&6));
+        assert_eq!(5, c.len());
+        assert_eq!(15, c.total().ok().unwrap());
+
+        let ret = Counter::from_result_iter(generate_okokerr_iterator(vec![1,2], error::overflow_error("[test]".to_string())));
+        assert_eq!(error::Kind::OverflowError, ret.err().unwrap().kind());
+    }
+
+    #[test]
+    fn test_most_common() {
+        let c = "abracadabra".chars().collect::<Counter<_>>();
+        assert_eq!(vec![('a', 5), ('b', 2), ('r', 2)], c.most_common(Some(3)));
+        assert_eq!(vec![('a', 5), ('b', 2), ('r', 2), ('c', 1), ('d', 1)], c.most_common(None));
+        assert_eq!(Vec::<(char, usize)>::new(), c.most_common(Some(0)));
+    }
+
+    #[test]
+    fn test_elements() {
+        let c = "abacb".chars().collect::<Counter<_>>();
+        assert_eq!("aabbc".to_string(), c.elements().collect::<String>());
+    }
+
+    #[test]
+    fn test_subtract() {
+        let mut c = "aaabbc".chars().collect::<Counter<_>>();
+        c.subtract(&"abbbd".chars().collect::<Counter<_>>());
+        assert_eq!("aac".chars().collect::<Counter<_>>(), c);
+    }
+
+    #[test]
+    fn test_arithmetic() {
+        let a = "aaab".chars().collect::<Counter<_>>();
+        let b = "abbc".chars().collect::<Counter<_>>();
+
+        assert_eq!("aaaabbbc".chars().collect::<Counter<_>>(), a.clone() + b.clone());
+        assert_eq!("aa".chars().collect::<Counter<_>>(), a.clone() - b.clone());
+        assert_eq!("ab".chars().collect::<Counter<_>>(), a.clone() & b.clone());
+        assert_eq!("aaabbc".chars().collect::<Counter<_>>(), a | b);
+    }
+}
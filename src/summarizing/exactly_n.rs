@@ -0,0 +1,41 @@
+use crate::error::Error;
+
+/// https://more-itertools.readthedocs.io/en/v10.2.0/api.html#more_itertools.exactly_n
+///
+/// Stops reading as soon as `n + 1` items satisfy `pred`.
+pub fn exactly_n<T>(iter: Box<dyn Iterator<Item = Result<T,Error>>>, n: usize, pred: fn(&T) -> bool) -> Result<bool, Error>
+{
+    let mut count = 0;
+    for item in iter {
+        if pred(&item?) {
+            count += 1;
+            if count > n {
+                return Ok(false);
+            }
+        }
+    }
+    Ok(count == n)
+}
+
+#[cfg(test)]
+mod tests {
+    use crate::error;
+    use crate::utils::{generate_okok_iterator, generate_okokerr_iterator};
+
+    use super::*;
+
+    #[test]
+    fn test1() {
+        assert!(exactly_n(generate_okok_iterator(vec![true, true, false]), 2, |x| *x).ok().unwrap());
+        assert!(!exactly_n(generate_okok_iterator(vec![true, true, false]), 3, |x| *x).ok().unwrap());
+        assert!(exactly_n(generate_okok_iterator(vec![1,2,3,4,5]), 3, |x| *x < 4).ok().unwrap());
+        assert!(exactly_n(generate_okok_iterator(Vec::<i32>::new()), 0, |x| *x < 4).ok().unwrap());
+    }
+
+    #[test]
+    fn test2_early_exit() {
+        assert!(!exactly_n(generate_okokerr_iterator(vec![1,2], error::overflow_error("[test]".to_string())), 1, |x| *x > 0).ok().unwrap());
+        assert_eq!(error::Kind::OverflowError,
+            exactly_n(generate_okokerr_iterator(vec![1,2], error::overflow_error("[test]".to_string())), 2, |x| *x > 0).err().unwrap().kind());
+    }
+}
@@ -0,0 +1,35 @@
+use crate::error::Error;
+
+/// https://more-itertools.readthedocs.io/en/v10.2.0/api.html#more_itertools.first_true
+pub fn first_true<T>(iter: Box<dyn Iterator<Item = Result<T,Error>>>, default: Option<T>, pred: fn(&T) -> bool) -> Result<Option<T>, Error>
+{
+    for item in iter {
+        let item = item?;
+        if pred(&item) {
+            return Ok(Some(item));
+        }
+    }
+    Ok(default)
+}
+
+#[cfg(test)]
+mod tests {
+    use crate::error;
+    use crate::utils::{generate_okok_iterator, generate_okokerr_iterator};
+
+    use super::*;
+
+    #[test]
+    fn test1() {
+        assert_eq!(Some(6), first_true(generate_okok_iterator(vec![1,3,6,7]), None, |x| *x % 2 == 0).ok().unwrap());
+        assert_eq!(None, first_true(generate_okok_iterator(vec![1,3]), None, |x| *x % 2 == 0).ok().unwrap());
+        assert_eq!(Some(0), first_true(generate_okok_iterator(vec![1,3]), Some(0), |x| *x % 2 == 0).ok().unwrap());
+    }
+
+    #[test]
+    fn test2_early_exit() {
+        assert_eq!(Some(2), first_true(generate_okokerr_iterator(vec![1,2], error::overflow_error("[test]".to_string())), None, |x| *x > 1).ok().unwrap());
+        assert_eq!(error::Kind::OverflowError,
+            first_true(generate_okokerr_iterator(vec![1,2], error::overflow_error("[test]".to_string())), None, |x| *x > 5).err().unwrap().kind());
+    }
+}
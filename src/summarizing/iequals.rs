@@ -0,0 +1,63 @@
+use crate::error::Error;
+
+/// https://more-itertools.readthedocs.io/en/v10.2.0/api.html#more_itertools.iequals
+///
+/// Returns `false` as soon as the inputs differ, including in length.
+pub fn iequals<T>(mut iter_vec: Vec<Box<dyn Iterator<Item = Result<T,Error>>>>) -> Result<bool, Error>
+where T: PartialEq
+{
+    loop {
+        let mut first: Option<Option<T>> = None;
+        for iter in iter_vec.iter_mut() {
+            let cur = iter.next().transpose()?;
+            match first.as_ref() {
+                None => { first = Some(cur); },
+                Some(f) => {
+                    if *f != cur {
+                        return Ok(false);
+                    }
+                }
+            }
+        }
+
+        match first {
+            None | Some(None) => { return Ok(true); },
+            Some(Some(_)) => { continue; }
+        }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use crate::error;
+    use crate::utils::{generate_okok_iterator, generate_okokerr_iterator};
+
+    use super::*;
+
+    #[test]
+    fn test1() {
+        let v = vec![generate_okok_iterator("abc".chars().collect::<Vec<_>>()),
+                    generate_okok_iterator("abc".chars().collect::<Vec<_>>()),
+                    generate_okok_iterator("abc".chars().collect::<Vec<_>>())];
+        assert!(iequals(v).ok().unwrap());
+
+        let v = vec![generate_okok_iterator("abc".chars().collect::<Vec<_>>()),
+                    generate_okok_iterator("acb".chars().collect::<Vec<_>>())];
+        assert!(!iequals(v).ok().unwrap());
+
+        let v = vec![generate_okok_iterator("abc".chars().collect::<Vec<_>>()),
+                    generate_okok_iterator("ab".chars().collect::<Vec<_>>())];
+        assert!(!iequals(v).ok().unwrap());
+    }
+
+    #[test]
+    fn test2_error() {
+        let v = vec![generate_okok_iterator(vec![1,2,3]),
+                    generate_okokerr_iterator(vec![1,2], error::overflow_error("[test]".to_string()))];
+        assert_eq!(error::Kind::OverflowError, iequals(v).err().unwrap().kind());
+
+        let v = vec![generate_okok_iterator(vec![1,3]),
+                    generate_okokerr_iterator(vec![1,2], error::overflow_error("[test]".to_string()))];
+        assert!(!iequals(v).ok().unwrap());
+    }
+}
@@ -0,0 +1,47 @@
+use crate::error::Error;
+
+/// https://more-itertools.readthedocs.io/en/v10.2.0/api.html#more_itertools.is_sorted
+///
+/// With `strict`, equal neighbours make the result `false`.
+pub fn is_sorted<T, K>(iter: Box<dyn Iterator<Item = Result<T,Error>>>, key: fn(&T) -> K, reverse: bool, strict: bool) -> Result<bool, Error>
+where K: PartialOrd
+{
+    let mut prev: Option<K> = None;
+    for item in iter {
+        let k = key(&item?);
+        if let Some(p) = prev.as_ref() {
+            let (a, b) = if reverse { (&k, p) } else { (p, &k) };
+            let in_order = if strict { a < b } else { a <= b };
+            if !in_order {
+                return Ok(false);
+            }
+        }
+        prev = Some(k);
+    }
+    Ok(true)
+}
+
+#[cfg(test)]
+mod tests {
+    use crate::error;
+    use crate::utils::{generate_okok_iterator, generate_okokerr_iterator};
+
+    use super::*;
+
+    #[test]
+    fn test1() {
+        assert!(is_sorted(generate_okok_iterator(vec![1,2,2,3]), |x| *x, false, false).ok().unwrap());
+        assert!(!is_sorted(generate_okok_iterator(vec![1,2,2,3]), |x| *x, false, true).ok().unwrap());
+        assert!(!is_sorted(generate_okok_iterator(vec![1,3,2]), |x| *x, false, false).ok().unwrap());
+        assert!(is_sorted(generate_okok_iterator(vec![5,4,4,1]), |x| *x, true, false).ok().unwrap());
+        assert!(!is_sorted(generate_okok_iterator(vec![5,4,4,1]), |x| *x, true, true).ok().unwrap());
+        assert!(is_sorted(generate_okok_iterator(vec!["1", "2", "10"]), |x| x.parse::<i32>().unwrap(), false, true).ok().unwrap());
+    }
+
+    #[test]
+    fn test2_early_exit() {
+        assert!(!is_sorted(generate_okokerr_iterator(vec![2,1], error::overflow_error("[test]".to_string())), |x| *x, false, false).ok().unwrap());
+        assert_eq!(error::Kind::OverflowError,
+            is_sorted(generate_okokerr_iterator(vec![1,2], error::overflow_error("[test]".to_string())), |x| *x, false, false).err().unwrap().kind());
+    }
+}
@@ -0,0 +1,56 @@
+use crate::error::{self, Error};
+
+/// https://more-itertools.readthedocs.io/en/v10.2.0/api.html#more_itertools.minmax
+///
+/// Among equal keys the first item is the minimum and the last one the maximum.
+pub fn minmax<T, K>(mut iter: Box<dyn Iterator<Item = Result<T,Error>>>, key: fn(&T) -> K) -> Result<(T, T), Error>
+where
+T: Clone,
+K: PartialOrd
+{
+    let first = match iter.next() {
+        None => { return Err(error::value_error("[minmax] iterable is empty".to_string())); },
+        Some(v) => v?
+    };
+
+    let mut lo_key = key(&first);
+    let mut hi_key = key(&first);
+    let mut lo = first.clone();
+    let mut hi = first;
+
+    for item in iter {
+        let item = item?;
+        let k = key(&item);
+        if k < lo_key {
+            lo = item.clone();
+            lo_key = key(&item);
+        }
+        if k >= hi_key {
+            hi = item;
+            hi_key = k;
+        }
+    }
+
+    Ok((lo, hi))
+}
+
+#[cfg(test)]
+mod tests {
+    use crate::utils::{generate_okok_iterator, generate_okokerr_iterator};
+
+    use super::*;
+
+    #[test]
+    fn test1() {
+        assert_eq!((1, 9), minmax(generate_okok_iterator(vec![5,1,9,3]), |x| *x).ok().unwrap());
+        assert_eq!(("a", "ccc"), minmax(generate_okok_iterator(vec!["bb", "a", "ccc", "dd"]), |x| x.len()).ok().unwrap());
+        assert_eq!(((1, 'a'), (2, 'd')), minmax(generate_okok_iterator(vec![(1, 'a'), (2, 'b'), (1, 'c'), (2, 'd')]), |x| x.0).ok().unwrap());
+    }
+
+    #[test]
+    fn test2_error() {
+        assert_eq!(error::Kind::ValueError, minmax(generate_okok_iterator(Vec::<i32>::new()), |x| *x).err().unwrap().kind());
+        assert_eq!(error::Kind::OverflowError,
+            minmax(generate_okokerr_iterator(vec![1,2], error::overflow_error("[test]".to_string())), |x| *x).err().unwrap().kind());
+    }
+}
@@ -1,3 +1,25 @@
 pub mod ilen;
 
-pub mod unique_to_each;
\ No newline at end of file
+pub mod unique_to_each;
+
+pub mod all_equal;
+
+pub mod all_unique;
+
+pub mod is_sorted;
+
+pub mod exactly_n;
+
+pub mod quantify;
+
+pub mod first_true;
+
+pub mod iequals;
+
+pub mod minmax;
+
+pub mod counter;
+
+pub mod stats;
+
+pub mod sketch;
\ No newline at end of file
@@ -0,0 +1,29 @@
+use crate::error::Error;
+
+/// https://more-itertools.readthedocs.io/en/v10.2.0/api.html#more_itertools.quantify
+pub fn quantify<T>(iter: Box<dyn Iterator<Item = Result<T,Error>>>, pred: fn(&T) -> bool) -> Result<usize, Error>
+{
+    let mut count = 0;
+    for item in iter {
+        if pred(&item?) {
+            count += 1;
+        }
+    }
+    Ok(count)
+}
+
+#[cfg(test)]
+mod tests {
+    use crate::error;
+    use crate::utils::{generate_okok_iterator, generate_okokerr_iterator};
+
+    use super::*;
+
+    #[test]
+    fn test1() {
+        assert_eq!(2, quantify(generate_okok_iterator(vec![true, false, true]), |x| *x).ok().unwrap());
+        assert_eq!(3, quantify(generate_okok_iterator(vec![1,2,3,4,5,6]), |x| *x % 2 == 0).ok().unwrap());
+        assert_eq!(error::Kind::OverflowError,
+            quantify(generate_okokerr_iterator(vec![1,2], error::overflow_error("[test]".to_string())), |x| *x > 0).err().unwrap().kind());
+    }
+}
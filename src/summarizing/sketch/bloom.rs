@@ -0,0 +1,130 @@
+use std::hash::Hash;
+
+use crate::error::{self, Error};
+use crate::selecting::unique_everseen::unique_everseen_with;
+use crate::summarizing::sketch::hash_with_seed;
+use crate::utils::SeenSet;
+
+/// Bloom filter sized for `capacity` items at false-positive rate `fp_rate`.
+///
+/// As a `SeenSet` it never forgets an item, but may claim to have seen an
+/// item it has not.
+#[derive(Clone, Debug, PartialEq)]
+pub struct BloomFilter {
+    bits: Vec<u64>,
+    nbits: u64,
+    nhashes: u32
+}
+
+impl BloomFilter {
+    pub fn new(capacity: usize, fp_rate: f64) -> Result<BloomFilter, Error> {
+        if capacity == 0 {
+            return Err(error::value_error("[bloom] capacity should not be 0".to_string()));
+        }
+        if !(fp_rate > 0.0 && fp_rate < 1.0) {
+            return Err(error::value_error(format!("[bloom] fp_rate {} is not in (0, 1)", fp_rate)));
+        }
+
+        let ln2 = std::f64::consts::LN_2;
+        let nbits = (-(capacity as f64) * fp_rate.ln() / (ln2 * ln2)).ceil().max(64.0) as u64;
+        let nhashes = ((nbits as f64 / capacity as f64) * ln2).round().max(1.0) as u32;
+
+        Ok(BloomFilter {
+            bits: vec![0; ((nbits + 63) / 64) as usize],
+            nbits,
+            nhashes
+        })
+    }
+
+    fn positions<T>(&self, item: &T) -> impl Iterator<Item = u64>
+    where T: Hash + ?Sized
+    {
+        let h1 = hash_with_seed(item, 0);
+        let h2 = hash_with_seed(item, 1) | 1;
+        let nbits = self.nbits;
+        (0..self.nhashes as u64).map(move |i| h1.wrapping_add(i.wrapping_mul(h2)) % nbits)
+    }
+
+    /// Sets the bits of `item`, returning `true` when at least one was unset.
+    pub fn insert<T>(&mut self, item: &T) -> bool
+    where T: Hash + ?Sized
+    {
+        let mut is_new = false;
+        for pos in self.positions(item).collect::<Vec<_>>() {
+            let (word, bit) = ((pos / 64) as usize, pos % 64);
+            if self.bits[word] & (1 << bit) == 0 {
+                is_new = true;
+                self.bits[word] |= 1 << bit;
+            }
+        }
+        is_new
+    }
+
+    pub fn contains<T>(&self, item: &T) -> bool
+    where T: Hash + ?Sized
+    {
+        self.positions(item).all(|pos| self.bits[(pos / 64) as usize] & (1 << (pos % 64)) != 0)
+    }
+}
+
+impl<K> SeenSet<K> for BloomFilter
+where K: Hash
+{
+    fn insert_key(&mut self, key: K) -> bool {
+        self.insert(&key)
+    }
+
+    fn contains_key(&self, key: &K) -> bool {
+        self.contains(key)
+    }
+}
+
+/// `unique_everseen` in bounded memory: unique items may be dropped as false
+/// duplicates, at a rate that rises as the filter fills and reaches `fp_rate`
+/// after `capacity` unique items.
+pub fn unique_everseen_approx<T, K>(iter: Box<dyn Iterator<Item = Result<T,Error>>>, key: fn(&T) -> K, capacity: usize, fp_rate: f64) -> Box<dyn Iterator<Item = Result<T,Error>>>
+where
+T: 'static,
+K: Hash + 'static
+{
+    match BloomFilter::new(capacity, fp_rate) {
+        Ok(seen) => unique_everseen_with(iter, key, seen),
+        Err(err) => Box::new(std::iter::once(Err(err)))
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use crate::utils::{extract_value_from_result_vec, generate_okok_iterator, generate_okokerr_iterator};
+
+    use super::*;
+
+    #[test]
+    fn test1() {
+        let mut bf = BloomFilter::new(1000, 0.01).ok().unwrap();
+        for i in 0..1000 {
+            bf.insert(&i);
+        }
+        assert!((0..1000).all(|i| bf.contains(&i)));
+
+        let false_positives = (1000..11000).filter(|i| bf.contains(i)).count();
+        assert!(false_positives < 300);
+    }
+
+    #[test]
+    fn test2_unique_everseen_approx() {
+        let ret = unique_everseen_approx(generate_okok_iterator("AAAABBBCCDAABBB".chars().collect::<Vec<_>>()), |x| *x, 100, 0.001);
+        assert_eq!(vec!['A','B','C','D'], extract_value_from_result_vec(ret.collect::<Vec<_>>()).0);
+    }
+
+    #[test]
+    fn test3_error() {
+        let mut ret = unique_everseen_approx(generate_okok_iterator(vec![1]), |x| *x, 100, 1.5);
+        assert_eq!(error::Kind::ValueError, ret.next().unwrap().err().unwrap().kind());
+        assert!(ret.next().is_none());
+
+        let mut ret = unique_everseen_approx(generate_okokerr_iterator(vec![1], error::overflow_error("[test]".to_string())), |x| *x, 100, 0.01);
+        assert_eq!(Some(Ok(1)), ret.next());
+        assert_eq!(error::Kind::OverflowError, ret.next().unwrap().err().unwrap().kind());
+    }
+}
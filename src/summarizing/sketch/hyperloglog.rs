@@ -0,0 +1,116 @@
+use std::hash::Hash;
+
+use crate::error::{self, Error};
+use crate::summarizing::sketch::hash_with_seed;
+
+/// Cardinality estimator using `2^precision` one-byte registers; the standard
+/// error is about `1.04 / sqrt(2^precision)`.
+#[derive(Clone, Debug, PartialEq)]
+pub struct HyperLogLog {
+    precision: u8,
+    registers: Vec<u8>
+}
+
+impl HyperLogLog {
+    pub fn new(precision: u8) -> Result<HyperLogLog, Error> {
+        if !(4..=16).contains(&precision) {
+            return Err(error::value_error(format!("[hyperloglog] precision {} is not in [4, 16]", precision)));
+        }
+
+        Ok(HyperLogLog {
+            precision,
+            registers: vec![0; 1 << precision]
+        })
+    }
+
+    pub fn insert<T>(&mut self, item: &T)
+    where T: Hash + ?Sized
+    {
+        let x = hash_with_seed(item, 0);
+        let p = self.precision as u32;
+        let index = (x >> (64 - p)) as usize;
+        let rank = ((x << p).leading_zeros() + 1).min(64 - p + 1) as u8;
+        if rank > self.registers[index] {
+            self.registers[index] = rank;
+        }
+    }
+
+    pub fn count(&self) -> usize {
+        let m = self.registers.len() as f64;
+        let alpha = match self.registers.len() {
+            16 => 0.673,
+            32 => 0.697,
+            64 => 0.709,
+            _ => 0.7213 / (1.0 + 1.079 / m)
+        };
+
+        let sum = self.registers.iter().map(|r| 2f64.powi(-(*r as i32))).sum::<f64>();
+        let estimate = alpha * m * m / sum;
+
+        let zeros = self.registers.iter().filter(|r| **r == 0).count();
+        if estimate <= 2.5 * m && zeros > 0 {
+            return (m * (m / zeros as f64).ln()).round() as usize;
+        }
+        estimate.round() as usize
+    }
+
+    /// Folds `other` into `self`, as if all its items had been inserted here.
+    pub fn merge(&mut self, other: &HyperLogLog) -> Result<(), Error> {
+        if self.precision != other.precision {
+            return Err(error::value_error("[hyperloglog] cannot merge sketches of different precision".to_string()));
+        }
+        for (r, o) in self.registers.iter_mut().zip(other.registers.iter()) {
+            *r = (*r).max(*o);
+        }
+        Ok(())
+    }
+}
+
+/// Estimated number of distinct items, in memory fixed by `precision`.
+pub fn approx_distinct<T>(iter: Box<dyn Iterator<Item = Result<T,Error>>>, precision: u8) -> Result<usize, Error>
+where T: Hash
+{
+    let mut hll = HyperLogLog::new(precision)?;
+    for item in iter {
+        hll.insert(&item?);
+    }
+    Ok(hll.count())
+}
+
+#[cfg(test)]
+mod tests {
+    use crate::utils::{generate_okok_iterator, generate_okokerr_iterator};
+
+    use super::*;
+
+    #[test]
+    fn test1() {
+        let v = (0..100000).map(|x| x % 20000).collect::<Vec<_>>();
+        let n = approx_distinct(generate_okok_iterator(v), 12).ok().unwrap();
+        assert!((n as f64 - 20000.0).abs() < 20000.0 * 0.05);
+
+        assert_eq!(3, approx_distinct(generate_okok_iterator(vec!["a", "b", "c", "a"]), 10).ok().unwrap());
+        assert_eq!(0, approx_distinct(generate_okok_iterator(Vec::<i32>::new()), 10).ok().unwrap());
+    }
+
+    #[test]
+    fn test2_merge() {
+        let mut a = HyperLogLog::new(10).ok().unwrap();
+        let mut b = HyperLogLog::new(10).ok().unwrap();
+        for i in 0..1000 {
+            a.insert(&i);
+            b.insert(&(i + 500));
+        }
+        a.merge(&b).ok().unwrap();
+        assert!((a.count() as f64 - 1500.0).abs() < 1500.0 * 0.1);
+
+        assert_eq!(error::Kind::ValueError, a.merge(&HyperLogLog::new(11).ok().unwrap()).err().unwrap().kind());
+    }
+
+    #[test]
+    fn test3_error() {
+        assert_eq!(error::Kind::ValueError, HyperLogLog::new(3).err().unwrap().kind());
+        assert_eq!(error::Kind::OverflowError,
+            approx_distinct(generate_okokerr_iterator(vec![1], error::overflow_error("[test]".to_string())), 10).err().unwrap().kind());
+    }
+}
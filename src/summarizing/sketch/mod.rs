@@ -0,0 +1,18 @@
+use std::collections::hash_map::DefaultHasher;
+use std::hash::{Hash, Hasher};
+
+pub mod hyperloglog;
+
+pub mod space_saving;
+
+pub mod bloom;
+
+// 64-bit hash of `item`, different for each `seed` and stable across runs.
+pub(crate) fn hash_with_seed<T>(item: &T, seed: u64) -> u64
+where T: Hash + ?Sized
+{
+    let mut hasher = DefaultHasher::new();
+    seed.hash(&mut hasher);
+    item.hash(&mut hasher);
+    hasher.finish()
+}
@@ -0,0 +1,127 @@
+use std::collections::{BTreeMap, HashMap};
+use std::hash::Hash;
+
+use crate::error::{self, Error};
+
+/// Space-Saving top-k summary (Metwally et al.) keeping at most `capacity` counters.
+///
+/// Any item occurring more than `n / capacity` times in a stream of `n` items
+/// is guaranteed to be monitored. Reported counts are upper bounds that
+/// overestimate by at most the item's `error`.
+#[derive(Clone, Debug)]
+pub struct SpaceSaving<T>
+where T: Hash + Eq + Clone
+{
+    capacity: usize,
+    // item -> (count, error, seq)
+    counters: HashMap<T, (usize, usize, u64)>,
+    // (count, seq) -> item, to find the smallest counter
+    by_count: BTreeMap<(usize, u64), T>,
+    next_seq: u64
+}
+
+impl<T> SpaceSaving<T>
+where T: Hash + Eq + Clone
+{
+    pub fn new(capacity: usize) -> Result<SpaceSaving<T>, Error> {
+        if capacity == 0 {
+            return Err(error::value_error("[space_saving] capacity should not be 0".to_string()));
+        }
+
+        Ok(SpaceSaving {
+            capacity,
+            counters: HashMap::new(),
+            by_count: BTreeMap::new(),
+            next_seq: 0
+        })
+    }
+
+    pub fn insert(&mut self, item: T) {
+        let seq = self.next_seq;
+        self.next_seq += 1;
+
+        if let Some((count, _, old_seq)) = self.counters.get_mut(&item) {
+            self.by_count.remove(&(*count, *old_seq));
+            *count += 1;
+            *old_seq = seq;
+            self.by_count.insert((*count, seq), item);
+            return;
+        }
+
+        if self.counters.len() < self.capacity {
+            self.counters.insert(item.clone(), (1, 0, seq));
+            self.by_count.insert((1, seq), item);
+            return;
+        }
+
+        let ((min_count, _), evicted) = self.by_count.pop_first().unwrap();
+        self.counters.remove(&evicted);
+        self.counters.insert(item.clone(), (min_count + 1, min_count, seq));
+        self.by_count.insert((min_count + 1, seq), item);
+    }
+
+    /// `(count, error)` of a monitored item: its true count lies in `[count - error, count]`.
+    pub fn estimate(&self, item: &T) -> Option<(usize, usize)> {
+        self.counters.get(item).map(|(count, err, _)| (*count, *err))
+    }
+
+    /// The `k` items with the highest counts, highest first.
+    pub fn top(&self, k: usize) -> Vec<(T, usize)> {
+        self.by_count.iter().rev().take(k).map(|((count, _), item)| (item.clone(), *count)).collect()
+    }
+}
+
+/// The `k` most frequent items with their estimated counts, using `10 * k` counters.
+pub fn heavy_hitters<T>(iter: Box<dyn Iterator<Item = Result<T,Error>>>, k: usize) -> Result<Vec<(T, usize)>, Error>
+where T: Hash + Eq + Clone
+{
+    let mut ss = SpaceSaving::new(k.saturating_mul(10))?;
+    for item in iter {
+        ss.insert(item?);
+    }
+    Ok(ss.top(k))
+}
+
+#[cfg(test)]
+mod tests {
+    use crate::utils::{generate_okok_iterator, generate_okokerr_iterator};
+
+    use super::*;
+
+    #[test]
+    fn test1() {
+        let mut v = Vec::new();
+        for i in 0..1000 {
+            v.push(i);
+            if i % 10 == 0 {
+                v.push(-1);
+                v.push(-1);
+                v.push(-2);
+            }
+        }
+
+        let top = heavy_hitters(generate_okok_iterator(v), 2).ok().unwrap();
+        assert_eq!(vec![-1, -2], top.iter().map(|x| x.0).collect::<Vec<_>>());
+        assert!(top[0].1 >= 200);
+        assert!(top[1].1 >= 100);
+    }
+
+    #[test]
+    fn test2_estimate() {
+        let mut ss = SpaceSaving::new(2).ok().unwrap();
+        for c in "aabac".chars() {
+            ss.insert(c);
+        }
+        assert_eq!(Some((3, 0)), ss.estimate(&'a'));
+        assert_eq!(Some((2, 1)), ss.estimate(&'c'));
+        assert_eq!(None, ss.estimate(&'b'));
+        assert_eq!(vec![('a', 3)], ss.top(1));
+    }
+
+    #[test]
+    fn test3_error() {
+        assert_eq!(error::Kind::ValueError, heavy_hitters(generate_okok_iterator(vec![1]), 0).err().unwrap().kind());
+        assert_eq!(error::Kind::OverflowError,
+            heavy_hitters(generate_okokerr_iterator(vec![1], error::overflow_error("[test]".to_string())), 1).err().unwrap().kind());
+    }
+}
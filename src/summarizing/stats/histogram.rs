@@ -0,0 +1,104 @@
+use crate::error::{self, Error};
+
+/// Counts of values falling into `bins` equal-width bins over `[lo, hi]`.
+///
+/// Every bin is half-open except the last one, which also holds `hi`; values
+/// outside the range are tallied in `underflow` and `overflow`.
+#[derive(Clone, Debug, PartialEq)]
+pub struct Histogram {
+    lo: f64,
+    hi: f64,
+    counts: Vec<usize>,
+    underflow: usize,
+    overflow: usize
+}
+
+impl Histogram {
+    pub fn new(lo: f64, hi: f64, bins: usize) -> Result<Histogram, Error> {
+        if bins == 0 {
+            return Err(error::value_error("[histogram] bins should not be 0".to_string()));
+        }
+        if !lo.is_finite() || !hi.is_finite() || lo >= hi {
+            return Err(error::value_error(format!("[histogram] invalid range [{}, {}]", lo, hi)));
+        }
+
+        Ok(Histogram {
+            lo,
+            hi,
+            counts: vec![0; bins],
+            underflow: 0,
+            overflow: 0
+        })
+    }
+
+    pub fn push(&mut self, x: f64) -> Result<(), Error> {
+        if x.is_nan() {
+            return Err(error::value_error("[histogram] NaN cannot be binned".to_string()));
+        }
+
+        if x < self.lo {
+            self.underflow += 1;
+        } else if x > self.hi {
+            self.overflow += 1;
+        } else {
+            let bins = self.counts.len();
+            let index = ((x - self.lo) / (self.hi - self.lo) * bins as f64) as usize;
+            self.counts[index.min(bins - 1)] += 1;
+        }
+        Ok(())
+    }
+
+    pub fn counts(&self) -> &[usize] {
+        &self.counts
+    }
+
+    /// The `bins + 1` bin boundaries, from `lo` to `hi`.
+    pub fn bin_edges(&self) -> Vec<f64> {
+        let bins = self.counts.len();
+        let width = (self.hi - self.lo) / bins as f64;
+        (0..=bins).map(|i| if i == bins { self.hi } else { self.lo + width * i as f64 }).collect()
+    }
+
+    pub fn underflow(&self) -> usize {
+        self.underflow
+    }
+
+    pub fn overflow(&self) -> usize {
+        self.overflow
+    }
+}
+
+pub fn histogram<T>(iter: Box<dyn Iterator<Item = Result<T,Error>>>, lo: f64, hi: f64, bins: usize) -> Result<Histogram, Error>
+where T: Into<f64>
+{
+    let mut ret = Histogram::new(lo, hi, bins)?;
+    for item in iter {
+        ret.push(item?.into())?;
+    }
+    Ok(ret)
+}
+
+#[cfg(test)]
+mod tests {
+    use crate::utils::{generate_okok_iterator, generate_okokerr_iterator};
+
+    use super::*;
+
+    #[test]
+    fn test1() {
+        let h = histogram(generate_okok_iterator(vec![-1.0, 0.0, 0.5, 1.0, 2.4, 2.5, 3.0, 4.0]), 0.0, 3.0, 3).ok().unwrap();
+        assert_eq!(&[2, 1, 3], h.counts());
+        assert_eq!(1, h.underflow());
+        assert_eq!(1, h.overflow());
+        assert_eq!(vec![0.0, 1.0, 2.0, 3.0], h.bin_edges());
+    }
+
+    #[test]
+    fn test2_error() {
+        assert_eq!(error::Kind::ValueError, Histogram::new(0.0, 1.0, 0).err().unwrap().kind());
+        assert_eq!(error::Kind::ValueError, Histogram::new(1.0, 1.0, 2).err().unwrap().kind());
+        assert_eq!(error::Kind::ValueError, histogram(generate_okok_iterator(vec![f64::NAN]), 0.0, 1.0, 2).err().unwrap().kind());
+        assert_eq!(error::Kind::OverflowError,
+            histogram(generate_okokerr_iterator(vec![1.0], error::overflow_error("[test]".to_string())), 0.0, 1.0, 2).err().unwrap().kind());
+    }
+}
@@ -0,0 +1,7 @@
+pub mod running;
+
+pub mod quantile;
+
+pub mod p_square;
+
+pub mod histogram;
\ No newline at end of file
@@ -0,0 +1,155 @@
+use std::cmp::Ordering;
+
+use crate::error::Error;
+use crate::summarizing::stats::quantile::{check_q, quantile_of_sorted};
+
+/// Streaming estimate of one quantile in constant memory, using the P²
+/// algorithm of Jain and Chlamtac (1985).
+#[derive(Clone, Debug)]
+pub struct P2Quantile {
+    p: f64,
+    count: usize,
+    // marker heights, actual positions, desired positions and their increments
+    q: [f64; 5],
+    n: [f64; 5],
+    np: [f64; 5],
+    dn: [f64; 5],
+    init: Vec<f64>
+}
+
+impl P2Quantile {
+    pub fn new(p: f64) -> Result<P2Quantile, Error> {
+        check_q(p)?;
+        Ok(P2Quantile {
+            p,
+            count: 0,
+            q: [0.0; 5],
+            n: [0.0; 5],
+            np: [0.0; 5],
+            dn: [0.0; 5],
+            init: Vec::with_capacity(5)
+        })
+    }
+
+    pub fn push(&mut self, x: f64) {
+        if x.is_nan() {
+            return;
+        }
+
+        self.count += 1;
+        if self.count <= 5 {
+            self.init.push(x);
+            if self.count == 5 {
+                self.init.sort_by(|a, b| a.partial_cmp(b).unwrap_or(Ordering::Equal));
+                let p = self.p;
+                self.q.copy_from_slice(&self.init);
+                self.n = [1.0, 2.0, 3.0, 4.0, 5.0];
+                self.np = [1.0, 1.0 + 2.0 * p, 1.0 + 4.0 * p, 3.0 + 2.0 * p, 5.0];
+                self.dn = [0.0, p / 2.0, p, (1.0 + p) / 2.0, 1.0];
+            }
+            return;
+        }
+
+        let k = if x < self.q[0] {
+            self.q[0] = x;
+            0
+        } else if x >= self.q[4] {
+            self.q[4] = x;
+            3
+        } else {
+            (1..5).find(|i| x < self.q[*i]).unwrap() - 1
+        };
+
+        for i in (k + 1)..5 {
+            self.n[i] += 1.0;
+        }
+        for i in 0..5 {
+            self.np[i] += self.dn[i];
+        }
+
+        for i in 1..4 {
+            let d = self.np[i] - self.n[i];
+            if (d >= 1.0 && self.n[i + 1] - self.n[i] > 1.0) || (d <= -1.0 && self.n[i - 1] - self.n[i] < -1.0) {
+                let d = d.signum();
+                let qp = self.parabolic(i, d);
+                if self.q[i - 1] < qp && qp < self.q[i + 1] {
+                    self.q[i] = qp;
+                } else {
+                    self.q[i] = self.linear(i, d);
+                }
+                self.n[i] += d;
+            }
+        }
+    }
+
+    fn parabolic(&self, i: usize, d: f64) -> f64 {
+        let (q, n) = (&self.q, &self.n);
+        q[i] + d / (n[i + 1] - n[i - 1])
+            * ((n[i] - n[i - 1] + d) * (q[i + 1] - q[i]) / (n[i + 1] - n[i])
+                + (n[i + 1] - n[i] - d) * (q[i] - q[i - 1]) / (n[i] - n[i - 1]))
+    }
+
+    fn linear(&self, i: usize, d: f64) -> f64 {
+        let j = if d > 0.0 { i + 1 } else { i - 1 };
+        self.q[i] + d * (self.q[j] - self.q[i]) / (self.n[j] - self.n[i])
+    }
+
+    pub fn count(&self) -> usize {
+        self.count
+    }
+
+    /// The current estimate; exact while fewer than five values were pushed.
+    pub fn value(&self) -> Option<f64> {
+        if self.count == 0 {
+            return None;
+        }
+        if self.count < 5 {
+            let mut sorted = self.init.clone();
+            sorted.sort_by(|a, b| a.partial_cmp(b).unwrap_or(Ordering::Equal));
+            return Some(quantile_of_sorted(&sorted, self.p));
+        }
+        Some(self.q[2])
+    }
+}
+
+/// Approximate `p`-quantile of an unbounded stream; `None` when it is empty.
+pub fn approx_quantile<T>(iter: Box<dyn Iterator<Item = Result<T,Error>>>, p: f64) -> Result<Option<f64>, Error>
+where T: Into<f64>
+{
+    let mut est = P2Quantile::new(p)?;
+    for item in iter {
+        est.push(item?.into());
+    }
+    Ok(est.value())
+}
+
+#[cfg(test)]
+mod tests {
+    use crate::error;
+    use crate::utils::{generate_okok_iterator, generate_okokerr_iterator};
+
+    use super::*;
+
+    #[test]
+    fn test1() {
+        let v = (0..10001).map(|x| ((x * 7919) % 10001) as f64).collect::<Vec<_>>();
+        let median = approx_quantile(generate_okok_iterator(v.clone()), 0.5).ok().unwrap().unwrap();
+        assert!((median - 5000.0).abs() < 50.0);
+
+        let p90 = approx_quantile(generate_okok_iterator(v), 0.9).ok().unwrap().unwrap();
+        assert!((p90 - 9000.0).abs() < 50.0);
+    }
+
+    #[test]
+    fn test2_small() {
+        assert_eq!(None, approx_quantile(generate_okok_iterator(Vec::<f64>::new()), 0.5).ok().unwrap());
+        assert_eq!(Some(2.0), approx_quantile(generate_okok_iterator(vec![3.0, 1.0, 2.0]), 0.5).ok().unwrap());
+    }
+
+    #[test]
+    fn test3_error() {
+        assert_eq!(error::Kind::ValueError, P2Quantile::new(-0.1).err().unwrap().kind());
+        assert_eq!(error::Kind::OverflowError,
+            approx_quantile(generate_okokerr_iterator(vec![1.0], error::overflow_error("[test]".to_string())), 0.5).err().unwrap().kind());
+    }
+}
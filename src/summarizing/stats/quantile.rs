@@ -0,0 +1,76 @@
+use std::cmp::Ordering;
+
+use crate::error::{self, Error};
+use crate::sequence::Sequence;
+
+pub(crate) fn check_q(q: f64) -> Result<(), Error> {
+    if !(0.0..=1.0).contains(&q) {
+        return Err(error::value_error(format!("quantile {} is not in [0, 1]", q)));
+    }
+    Ok(())
+}
+
+// Linear interpolation between closest ranks on sorted data (numpy's default method).
+pub(crate) fn quantile_of_sorted(sorted: &[f64], q: f64) -> f64 {
+    let h = (sorted.len() - 1) as f64 * q;
+    let lo = h.floor() as usize;
+    let hi = h.ceil() as usize;
+    sorted[lo] + (h - lo as f64) * (sorted[hi] - sorted[lo])
+}
+
+/// Exact quantiles of a materialized sequence, one per entry of `qs`.
+pub fn quantiles<T>(seq: &dyn Sequence<T>, qs: &[f64]) -> Result<Vec<f64>, Error>
+where T: Into<f64> + Copy
+{
+    if seq.len() == 0 {
+        return Err(error::value_error("[quantiles] sequence is empty".to_string()));
+    }
+
+    let mut sorted = seq.as_slice().iter().map(|x| (*x).into()).collect::<Vec<f64>>();
+    if sorted.iter().any(|x| x.is_nan()) {
+        return Err(error::value_error("[quantiles] sequence contains NaN".to_string()));
+    }
+    sorted.sort_by(|a, b| a.partial_cmp(b).unwrap_or(Ordering::Equal));
+
+    let mut ret = Vec::with_capacity(qs.len());
+    for q in qs.iter() {
+        check_q(*q)?;
+        ret.push(quantile_of_sorted(&sorted, *q));
+    }
+    Ok(ret)
+}
+
+pub fn median<T>(seq: &dyn Sequence<T>) -> Result<f64, Error>
+where T: Into<f64> + Copy
+{
+    Ok(quantiles(seq, &[0.5])?[0])
+}
+
+#[cfg(test)]
+mod tests {
+    use crate::sequence::create_seq_from_vec;
+
+    use super::*;
+
+    #[test]
+    fn test1() {
+        let seq = create_seq_from_vec(vec![3, 1, 4, 1, 5, 9, 2, 6]);
+        assert_eq!(vec![1.0, 3.5, 9.0], quantiles(seq.as_ref(), &[0.0, 0.5, 1.0]).ok().unwrap());
+        assert_eq!(vec![1.75], quantiles(seq.as_ref(), &[0.25]).ok().unwrap());
+
+        let seq = create_seq_from_vec(vec![1.0, 2.0, 10.0]);
+        assert_eq!(2.0, median(seq.as_ref()).ok().unwrap());
+    }
+
+    #[test]
+    fn test2_error() {
+        let seq = create_seq_from_vec(Vec::<f64>::new());
+        assert_eq!(error::Kind::ValueError, median(seq.as_ref()).err().unwrap().kind());
+
+        let seq = create_seq_from_vec(vec![1.0, f64::NAN]);
+        assert_eq!(error::Kind::ValueError, median(seq.as_ref()).err().unwrap().kind());
+
+        let seq = create_seq_from_vec(vec![1.0]);
+        assert_eq!(error::Kind::ValueError, quantiles(seq.as_ref(), &[1.5]).err().unwrap().kind());
+    }
+}
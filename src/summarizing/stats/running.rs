@@ -0,0 +1,114 @@
+use crate::error::{self, Error};
+
+/// Online count, mean, variance, min and max (Welford's algorithm).
+#[derive(Clone, Debug, Default, PartialEq)]
+pub struct RunningStats {
+    count: usize,
+    mean: f64,
+    m2: f64,
+    min: Option<f64>,
+    max: Option<f64>
+}
+
+impl RunningStats {
+    pub fn new() -> RunningStats {
+        RunningStats::default()
+    }
+
+    pub fn push(&mut self, x: f64) {
+        self.count += 1;
+        let delta = x - self.mean;
+        self.mean += delta / self.count as f64;
+        self.m2 += delta * (x - self.mean);
+
+        self.min = Some(self.min.map_or(x, |m| m.min(x)));
+        self.max = Some(self.max.map_or(x, |m| m.max(x)));
+    }
+
+    pub fn count(&self) -> usize {
+        self.count
+    }
+
+    pub fn mean(&self) -> Option<f64> {
+        if self.count == 0 {
+            return None;
+        }
+        Some(self.mean)
+    }
+
+    /// Variance with `ddof` delta degrees of freedom: 0 for the population
+    /// variance, 1 for the sample variance.
+    pub fn variance(&self, ddof: usize) -> Option<f64> {
+        if self.count <= ddof {
+            return None;
+        }
+        Some(self.m2 / (self.count - ddof) as f64)
+    }
+
+    pub fn std_dev(&self, ddof: usize) -> Option<f64> {
+        self.variance(ddof).map(f64::sqrt)
+    }
+
+    pub fn min(&self) -> Option<f64> {
+        self.min
+    }
+
+    pub fn max(&self) -> Option<f64> {
+        self.max
+    }
+}
+
+pub fn running_stats<T>(iter: Box<dyn Iterator<Item = Result<T,Error>>>) -> Result<RunningStats, Error>
+where T: Into<f64>
+{
+    let mut ret = RunningStats::new();
+    for item in iter {
+        ret.push(item?.into());
+    }
+    Ok(ret)
+}
+
+pub fn mean<T>(iter: Box<dyn Iterator<Item = Result<T,Error>>>) -> Result<f64, Error>
+where T: Into<f64>
+{
+    running_stats(iter)?.mean().ok_or_else(|| error::value_error("[mean] requires at least one data point".to_string()))
+}
+
+pub fn variance<T>(iter: Box<dyn Iterator<Item = Result<T,Error>>>, ddof: usize) -> Result<f64, Error>
+where T: Into<f64>
+{
+    running_stats(iter)?.variance(ddof).ok_or_else(|| error::value_error(format!("[variance] requires more than {} data points", ddof)))
+}
+
+#[cfg(test)]
+mod tests {
+    use crate::utils::{generate_okok_iterator, generate_okokerr_iterator};
+
+    use super::*;
+
+    #[test]
+    fn test1() {
+        let s = running_stats(generate_okok_iterator(vec![2, 4, 4, 4, 5, 5, 7, 9])).ok().unwrap();
+        assert_eq!(8, s.count());
+        assert_eq!(Some(5.0), s.mean());
+        assert_eq!(Some(4.0), s.variance(0));
+        assert_eq!(Some(2.0), s.std_dev(0));
+        assert!((s.variance(1).unwrap() - 32.0 / 7.0).abs() < 1e-12);
+        assert_eq!(Some(2.0), s.min());
+        assert_eq!(Some(9.0), s.max());
+    }
+
+    #[test]
+    fn test2_stable() {
+        let v = vec![1e9 + 4.0, 1e9 + 7.0, 1e9 + 13.0, 1e9 + 16.0];
+        assert!((variance(generate_okok_iterator(v), 1).ok().unwrap() - 30.0).abs() < 1e-6);
+    }
+
+    #[test]
+    fn test3_error() {
+        assert_eq!(error::Kind::ValueError, mean(generate_okok_iterator(Vec::<f64>::new())).err().unwrap().kind());
+        assert_eq!(error::Kind::ValueError, variance(generate_okok_iterator(vec![1.0]), 1).err().unwrap().kind());
+        assert_eq!(error::Kind::OverflowError,
+            mean(generate_okokerr_iterator(vec![1.0], error::overflow_error("[test]".to_string()))).err().unwrap().kind());
+    }
+}
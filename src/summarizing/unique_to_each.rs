@@ -1,41 +1,103 @@
-// use std::collections::HashSet;
-
-// use crate::{error, error::Error, sequence::{create_seq_from_iterator_result, Sequence}};
-
-
-// pub fn unique_to_each<T>(iter_vec: Vec<Box<dyn Iterator<Item = Result<T,Error>>>>) -> Result<Vec<Box<dyn Sequence<T>>>, Error> 
-// where T: 'static
-// {
-//     let mut seq_vec: Vec<Box<dyn Sequence<T>>> = Vec::new();
-//     let mut result: Vec<Box<dyn Sequence<T>>> = Vec::new();
-    
-//     for iter in iter_vec.into_iter() {
-//         let seq_result = create_seq_from_iterator_result(iter)?;
-//         seq_vec.push(seq_result);
-//     }
-
-//     return Ok(result);
-// }
-
-// #[cfg(test)]
-// mod tests {
-//     use crate::utils::{generate_okok_iterator, generate_okokerr_iterator};
-
-//     use super::*;
-
-//     #[test]
-//     fn test1() {
-//         let v1 = generate_okok_iterator(vec![1,2,3]);
-//         let v2 = generate_okokerr_iterator(vec![1,2,3], error::overflow_error("overflow".to_string()));
-        
-//         let mut v = Vec::new();
-//         v.push(v1);
-//         v.push(v2);
-        
-//         let result = unique_to_each(v);
-//         if let Err(err) = result {
-//             assert_eq!(error::Kind::OverflowError, err.kind());
-//         }
-//     }
-// }
+use std::collections::HashSet;
+use std::hash::Hash;
 
+use crate::error::Error;
+use crate::sequence::{create_seq_from_vec, Sequence};
+use crate::utils::SeenSet;
+
+/// https://more-itertools.readthedocs.io/en/v10.2.0/api.html#more_itertools.unique_to_each
+pub fn unique_to_each<T>(iter_vec: Vec<Box<dyn Iterator<Item = Result<T,Error>>>>) -> Result<Vec<Box<dyn Sequence<T>>>, Error> 
+where T: Hash + Eq + Clone + 'static
+{
+    unique_to_each_with(iter_vec, HashSet::new())
+}
+
+/// `unique_to_each` using clones of `seen` to record the distinct elements of
+/// each input, e.g. a `BTreeSet` or a `SeenVec` for elements that are not hashable.
+pub fn unique_to_each_with<T, S>(iter_vec: Vec<Box<dyn Iterator<Item = Result<T,Error>>>>, seen: S) -> Result<Vec<Box<dyn Sequence<T>>>, Error> 
+where
+T: Clone + 'static,
+S: SeenSet<T> + Clone
+{
+    let mut pool: Vec<Vec<T>> = Vec::new();
+    let mut seen_vec: Vec<S> = Vec::new();
+
+    for iter in iter_vec.into_iter() {
+        let mut items = Vec::new();
+        let mut items_seen = seen.clone();
+        for item in iter {
+            let item = item?;
+            items_seen.insert_key(item.clone());
+            items.push(item);
+        }
+        pool.push(items);
+        seen_vec.push(items_seen);
+    }
+
+    let mut result: Vec<Box<dyn Sequence<T>>> = Vec::new();
+    for (i, items) in pool.into_iter().enumerate() {
+        let uniques = items.into_iter().filter(|item| {
+            !seen_vec.iter().enumerate().any(|(j, other)| j != i && other.contains_key(item))
+        }).collect::<Vec<_>>();
+        result.push(create_seq_from_vec(uniques));
+    }
+
+    Ok(result)
+}
+
+#[cfg(test)]
+mod tests {
+    use crate::error;
+    use crate::utils::{generate_okok_iterator, generate_okokerr_iterator, SeenVec};
+
+    use super::*;
+
+    #[test]
+    fn test1() {
+        let v: Vec<Box<dyn Iterator<Item = Result<char,Error>>>> = vec![
+            generate_okok_iterator("mississippi".chars().collect::<Vec<_>>()),
+            generate_okok_iterator("missouri".chars().collect::<Vec<_>>())
+        ];
+
+        let result = unique_to_each(v).ok().unwrap();
+        assert_eq!(vec!['p', 'p'], result[0].as_slice());
+        assert_eq!(vec!['o', 'u', 'r'], result[1].as_slice());
+    }
+
+    #[test]
+    fn test2_three_inputs() {
+        let v = vec![
+            generate_okok_iterator(vec![1, 2]),
+            generate_okok_iterator(vec![2, 3]),
+            generate_okok_iterator(vec![3, 4, 4])
+        ];
+
+        let result = unique_to_each(v).ok().unwrap();
+        assert_eq!(vec![1], result[0].as_slice());
+        assert!(result[1].as_slice().is_empty());
+        assert_eq!(vec![4, 4], result[2].as_slice());
+    }
+
+    #[test]
+    fn test3_unhashable() {
+        let v = vec![
+            generate_okok_iterator(vec![vec![1.0], vec![2.0]]),
+            generate_okok_iterator(vec![vec![2.0], vec![3.0]])
+        ];
+
+        let result = unique_to_each_with(v, SeenVec::new()).ok().unwrap();
+        assert_eq!(vec![vec![1.0]], result[0].as_slice());
+        assert_eq!(vec![vec![3.0]], result[1].as_slice());
+    }
+
+    #[test]
+    fn test4_error() {
+        let v = vec![
+            generate_okok_iterator(vec![1, 2, 3]),
+            generate_okokerr_iterator(vec![1, 2, 3], error::overflow_error("overflow".to_string()))
+        ];
+
+        let result = unique_to_each(v);
+        assert_eq!(error::Kind::OverflowError, result.err().unwrap().kind());
+    }
+}
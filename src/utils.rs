@@ -1,6 +1,6 @@
 use crate::error;
 use crate::error::Error;
-use std::collections::{HashMap, VecDeque};
+use std::collections::{BTreeSet, HashSet, VecDeque};
 use std::hash::Hash;
 
 pub fn extract_value_from_result_vec<T>(vec: Vec<Result<T, Error>>) -> (Vec<T>, Option<Error>) {
@@ -85,37 +85,85 @@ pub fn argsort<T: Ord>(data: &[T]) -> Vec<usize> {
 }
 
 
-pub fn counter<T>(hm: &mut HashMap<T, usize>, mut iter: Box<dyn Iterator<Item = Result<T,Error>>>) -> Result<(), Error>
-where T: Hash + Eq + PartialEq
-{
-    loop {
-        let _next = iter.next();
-
-        if let Some(res) = _next {
-            match res {
-                Ok(key) => {
-                    let value = hm.get_mut(&key);
-                    let add_res;
-                    if value.is_none() {
-                        add_res = (1, false);
-                    } else {
-                        add_res = usize::overflowing_add(*value.unwrap(), 1);
-                    }
-                    
-                    if add_res.1 {
-                        return Err(error::overflow_error("[counter] overflow".to_string()));
-                    } else {
-                        hm.insert(key, add_res.0);
-                        continue;
-                    }
-                },
-                Err(err) => {
-                    return Err(error::any_error(err.kind(), err.message().unwrap().clone()));
-                }
+/// Reorders `v` so that `v[i]` becomes the old `v[perm[i]]`, by swapping
+/// along the cycles of `perm` instead of cloning.
+pub fn apply_permutation<T>(v: &mut [T], perm: &[usize]) {
+    let mut done = vec![false; perm.len()];
+    for start in 0..perm.len() {
+        if done[start] {
+            continue;
+        }
+        let mut cur = start;
+        loop {
+            done[cur] = true;
+            let next = perm[cur];
+            if next == start {
+                break;
             }
-        } else {
-            return Ok(());
+            v.swap(cur, next);
+            cur = next;
+        }
+    }
+}
+
+/// The set of keys already met by the `*_everseen` family.
+///
+/// `HashSet` is the fast path; `BTreeSet` (keys only `Ord`) and `SeenVec`
+/// (keys only `PartialEq`, linear lookup) serve items that cannot be hashed.
+pub trait SeenSet<K> {
+    /// Records `key`, returning `true` when it had not been seen before.
+    fn insert_key(&mut self, key: K) -> bool;
+    fn contains_key(&self, key: &K) -> bool;
+}
+
+impl<K> SeenSet<K> for HashSet<K>
+where K: Hash + Eq
+{
+    fn insert_key(&mut self, key: K) -> bool {
+        self.insert(key)
+    }
+
+    fn contains_key(&self, key: &K) -> bool {
+        self.contains(key)
+    }
+}
+
+impl<K> SeenSet<K> for BTreeSet<K>
+where K: Ord
+{
+    fn insert_key(&mut self, key: K) -> bool {
+        self.insert(key)
+    }
+
+    fn contains_key(&self, key: &K) -> bool {
+        self.contains(key)
+    }
+}
+
+#[derive(Clone, Debug, Default)]
+pub struct SeenVec<K> {
+    v: Vec<K>
+}
+
+impl<K> SeenVec<K> {
+    pub fn new() -> SeenVec<K> {
+        SeenVec { v: Vec::new() }
+    }
+}
+
+impl<K> SeenSet<K> for SeenVec<K>
+where K: PartialEq
+{
+    fn insert_key(&mut self, key: K) -> bool {
+        if self.v.contains(&key) {
+            return false;
         }
+        self.v.push(key);
+        true
+    }
+
+    fn contains_key(&self, key: &K) -> bool {
+        self.v.contains(key)
     }
 }
 
@@ -185,6 +233,34 @@ T: Clone + 'static
     });
 }
 
+struct OkokLen<T> {
+    ok_vec: std::vec::IntoIter<T>,
+    len: usize
+}
+
+impl<T> Iterator for OkokLen<T> {
+    type Item = Result<T, Error>;
+
+    fn next(&mut self) -> Option<Self::Item> {
+        self.ok_vec.next().map(Ok)
+    }
+
+    fn size_hint(&self) -> (usize, Option<usize>) {
+        (self.len, Some(self.len))
+    }
+}
+
+impl<T> ExactSizeIterator for OkokLen<T> {}
+
+/// Like `generate_okok_iterator`, but an `ExactSizeIterator` whose length is
+/// `len`, right or not.
+pub fn generate_okok_len_iterator<T>(ok_vec: Vec<T>, len: usize) -> impl ExactSizeIterator<Item = Result<T, Error>> {
+    OkokLen {
+        ok_vec: ok_vec.into_iter(),
+        len
+    }
+}
+
 pub fn vecdeque_2_vec<T>(vd: &mut VecDeque<T>) -> Vec<T> {
     let mut ret = Vec::<T>::new();
     while vd.len() > 0 {
@@ -247,15 +323,28 @@ mod tests {
     }
 
     #[test]
-    fn test_counter() {
-        let v = vec![5,1,2,2,3,3,3,4,4,4,4,5,5,5,5];
-        let mut hm = HashMap::new();
-        let _ = counter(&mut hm, generate_okok_iterator(v));
-        assert_eq!(&1, hm.get(&1).unwrap());
-        assert_eq!(&2, hm.get(&2).unwrap());
-        assert_eq!(&3, hm.get(&3).unwrap());
-        assert_eq!(&4, hm.get(&4).unwrap());
-        assert_eq!(&5, hm.get(&5).unwrap());
+    fn test_apply_permutation() {
+        let mut v = vec!['a', 'b', 'c', 'd', 'e'];
+        let perm = argsort([3, 1, 4, 0, 2].as_slice());
+        apply_permutation(&mut v, &perm);
+        assert_eq!(vec!['d', 'b', 'e', 'a', 'c'], v);
+
+        let mut v = vec![String::from("x"), String::from("y")];
+        apply_permutation(&mut v, &[1, 0]);
+        assert_eq!(vec![String::from("y"), String::from("x")], v);
+    }
+
+    #[test]
+    fn test_seen_set() {
+        let mut seen = SeenVec::new();
+        assert!(seen.insert_key(vec![1]));
+        assert!(!seen.insert_key(vec![1]));
+        assert!(seen.contains_key(&vec![1]));
+        assert!(!seen.contains_key(&vec![2]));
+
+        let mut seen = BTreeSet::new();
+        assert!(seen.insert_key(1.to_string()));
+        assert!(!seen.insert_key(1.to_string()));
     }
 
     #[test]
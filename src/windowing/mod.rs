@@ -4,4 +4,7 @@ pub mod substrings;
 pub mod substrings_indexes;
 pub mod windowed_complete;
 pub mod pairwise;
-pub mod triplewise;
\ No newline at end of file
+pub mod triplewise;
+pub mod rolling;
+pub mod stagger;
+pub mod time_window;
\ No newline at end of file
@@ -0,0 +1,274 @@
+use std::collections::VecDeque;
+use std::ops::Add;
+
+use crate::error::{self, Error};
+
+/// A FIFO queue whose fold under an associative `op` is available in O(1)
+/// amortized time, using the two-stack technique.
+///
+/// `op` need not be commutative: elements are always combined oldest first.
+pub struct TwoStackAggregator<T> {
+    // oldest element on top; each entry holds (value, fold of value and the entries below it)
+    front: Vec<(T, T)>,
+    back: Vec<T>,
+    back_agg: Option<T>,
+    op: fn(&T, &T) -> T
+}
+
+impl<T> TwoStackAggregator<T>
+where T: Clone
+{
+    pub fn new(op: fn(&T, &T) -> T) -> TwoStackAggregator<T> {
+        TwoStackAggregator {
+            front: Vec::new(),
+            back: Vec::new(),
+            back_agg: None,
+            op
+        }
+    }
+
+    pub fn len(&self) -> usize {
+        self.front.len() + self.back.len()
+    }
+
+    pub fn is_empty(&self) -> bool {
+        self.len() == 0
+    }
+
+    pub fn push(&mut self, v: T) {
+        self.back_agg = Some(match self.back_agg.as_ref() {
+            None => v.clone(),
+            Some(agg) => (self.op)(agg, &v)
+        });
+        self.back.push(v);
+    }
+
+    pub fn pop(&mut self) -> Option<T> {
+        if self.front.is_empty() {
+            while let Some(v) = self.back.pop() {
+                let agg = match self.front.last() {
+                    None => v.clone(),
+                    Some((_, agg)) => (self.op)(&v, agg)
+                };
+                self.front.push((v, agg));
+            }
+            self.back_agg = None;
+        }
+        self.front.pop().map(|(v, _)| v)
+    }
+
+    pub fn query(&self) -> Option<T> {
+        match (self.front.last(), self.back_agg.as_ref()) {
+            (None, None) => None,
+            (Some((_, f)), None) => Some(f.clone()),
+            (None, Some(b)) => Some(b.clone()),
+            (Some((_, f)), Some(b)) => Some((self.op)(f, b))
+        }
+    }
+}
+
+struct Rolling<T> {
+    iter: Box<dyn Iterator<Item = Result<T,Error>>>,
+    n: usize,
+    agg: TwoStackAggregator<T>,
+    iter_finished: bool
+}
+
+impl<T> Iterator for Rolling<T>
+where T: Clone
+{
+    type Item = Result<T,Error>;
+
+    fn next(&mut self) -> Option<Self::Item> {
+        if self.iter_finished {
+            return None;
+        }
+
+        if self.n == 0 {
+            self.iter_finished = true;
+            return Some(Err(error::value_error("[rolling] n should not be 0".to_string())));
+        }
+
+        loop {
+            match self.iter.next() {
+                None => {
+                    self.iter_finished = true;
+                    return None;
+                },
+                Some(Err(err)) => {
+                    self.iter_finished = true;
+                    return Some(Err(err));
+                },
+                Some(Ok(v)) => {
+                    if self.agg.len() == self.n {
+                        self.agg.pop();
+                    }
+                    self.agg.push(v);
+                    if self.agg.len() == self.n {
+                        return self.agg.query().map(Ok);
+                    }
+                }
+            }
+        }
+    }
+}
+
+/// Folds every full window of `n` consecutive items with the associative `op`,
+/// in O(1) amortized time per item.
+pub fn rolling<T>(iter: Box<dyn Iterator<Item = Result<T,Error>>>, n: usize, op: fn(&T, &T) -> T) -> Box<dyn Iterator<Item = Result<T,Error>>>
+where T: Clone + 'static
+{
+    Box::new(Rolling {
+        iter,
+        n,
+        agg: TwoStackAggregator::new(op),
+        iter_finished: false
+    })
+}
+
+pub fn rolling_sum<T>(iter: Box<dyn Iterator<Item = Result<T,Error>>>, n: usize) -> Box<dyn Iterator<Item = Result<T,Error>>>
+where T: Add<Output = T> + Clone + 'static
+{
+    rolling(iter, n, |a, b| a.clone() + b.clone())
+}
+
+pub fn rolling_mean<T>(iter: Box<dyn Iterator<Item = Result<T,Error>>>, n: usize) -> Box<dyn Iterator<Item = Result<f64,Error>>>
+where T: Into<f64> + 'static
+{
+    let sums = rolling_sum(Box::new(iter.map(|x| x.map(Into::into))), n);
+    Box::new(sums.map(move |x| x.map(|sum: f64| sum / n as f64)))
+}
+
+struct RollingExtreme<T> {
+    iter: Box<dyn Iterator<Item = Result<T,Error>>>,
+    n: usize,
+    // candidates (index, value), monotonic so that the front is the extreme
+    deque: VecDeque<(usize, T)>,
+    index: usize,
+    keep_max: bool,
+    iter_finished: bool
+}
+
+impl<T> Iterator for RollingExtreme<T>
+where T: PartialOrd + Clone
+{
+    type Item = Result<T,Error>;
+
+    fn next(&mut self) -> Option<Self::Item> {
+        if self.iter_finished {
+            return None;
+        }
+
+        if self.n == 0 {
+            self.iter_finished = true;
+            return Some(Err(error::value_error("[rolling] n should not be 0".to_string())));
+        }
+
+        loop {
+            match self.iter.next() {
+                None => {
+                    self.iter_finished = true;
+                    return None;
+                },
+                Some(Err(err)) => {
+                    self.iter_finished = true;
+                    return Some(Err(err));
+                },
+                Some(Ok(v)) => {
+                    while let Some((_, back)) = self.deque.back() {
+                        let dominated = if self.keep_max { *back <= v } else { *back >= v };
+                        if !dominated {
+                            break;
+                        }
+                        self.deque.pop_back();
+                    }
+                    self.deque.push_back((self.index, v));
+                    self.index += 1;
+
+                    if self.index < self.n {
+                        continue;
+                    }
+                    while self.deque.front().is_some_and(|(i, _)| *i + self.n < self.index) {
+                        self.deque.pop_front();
+                    }
+                    return self.deque.front().map(|(_, v)| Ok(v.clone()));
+                }
+            }
+        }
+    }
+}
+
+pub fn rolling_min<T>(iter: Box<dyn Iterator<Item = Result<T,Error>>>, n: usize) -> Box<dyn Iterator<Item = Result<T,Error>>>
+where T: PartialOrd + Clone + 'static
+{
+    Box::new(RollingExtreme {
+        iter,
+        n,
+        deque: VecDeque::new(),
+        index: 0,
+        keep_max: false,
+        iter_finished: false
+    })
+}
+
+pub fn rolling_max<T>(iter: Box<dyn Iterator<Item = Result<T,Error>>>, n: usize) -> Box<dyn Iterator<Item = Result<T,Error>>>
+where T: PartialOrd + Clone + 'static
+{
+    Box::new(RollingExtreme {
+        iter,
+        n,
+        deque: VecDeque::new(),
+        index: 0,
+        keep_max: true,
+        iter_finished: false
+    })
+}
+
+
+#[cfg(test)]
+mod tests {
+    use crate::utils::{extract_value_from_result_vec, generate_okok_iterator, generate_okokerr_iterator};
+
+    use super::*;
+
+    #[test]
+    fn test_rolling_sum_mean() {
+        let ret = rolling_sum(generate_okok_iterator(vec![1,2,3,4,5]), 3);
+        assert_eq!(vec![6,9,12], extract_value_from_result_vec(ret.collect::<Vec<_>>()).0);
+
+        let ret = rolling_mean(generate_okok_iterator(vec![1,2,3,4,5]), 2);
+        assert_eq!(vec![1.5,2.5,3.5,4.5], extract_value_from_result_vec(ret.collect::<Vec<_>>()).0);
+
+        let ret = rolling_sum(generate_okok_iterator(vec![1,2]), 3);
+        assert_eq!(0, ret.count());
+    }
+
+    #[test]
+    fn test_rolling_min_max() {
+        let v = vec![4,2,12,3,8,1,1,7];
+        let ret = rolling_min(generate_okok_iterator(v.clone()), 3);
+        assert_eq!(vec![2,2,3,1,1,1], extract_value_from_result_vec(ret.collect::<Vec<_>>()).0);
+
+        let ret = rolling_max(generate_okok_iterator(v), 3);
+        assert_eq!(vec![12,12,12,8,8,7], extract_value_from_result_vec(ret.collect::<Vec<_>>()).0);
+    }
+
+    #[test]
+    fn test_rolling_non_commutative() {
+        let v = "abcdef".chars().map(|c| c.to_string()).collect::<Vec<_>>();
+        let ret = rolling(generate_okok_iterator(v), 3, |a, b| a.clone() + b);
+        assert_eq!(vec!["abc", "bcd", "cde", "def"], extract_value_from_result_vec(ret.collect::<Vec<_>>()).0);
+    }
+
+    #[test]
+    fn test_error() {
+        let mut ret = rolling_sum(generate_okok_iterator(vec![1,2]), 0);
+        assert_eq!(error::Kind::ValueError, ret.next().unwrap().err().unwrap().kind());
+        assert_eq!(None, ret.next());
+
+        let mut ret = rolling_max(generate_okokerr_iterator(vec![1,2], error::overflow_error("[test]".to_string())), 2);
+        assert_eq!(Some(Ok(2)), ret.next());
+        assert_eq!(error::Kind::OverflowError, ret.next().unwrap().err().unwrap().kind());
+        assert_eq!(None, ret.next());
+    }
+}
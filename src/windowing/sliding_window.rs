@@ -1,6 +1,6 @@
 use std::fmt::Debug;
 
-use super::windowed::windowed;
+use super::windowed::{windowed, WindowFill};
 use crate::error::Error;
 
 pub struct SlidingWindow<T>
@@ -25,7 +25,7 @@ pub fn sliding_windowed<T>(iter: Box<dyn Iterator<Item=Result<T,Error>>>, n: usi
 where
 T: Clone + Debug + 'static
 {
-    let ret = windowed(iter, n, 1);
+    let ret = windowed(iter, n, 1, WindowFill::Drop);
 
     return ret;
 }
@@ -0,0 +1,68 @@
+use crate::combining::zip_offset::zip_offset;
+use crate::error::Error;
+use crate::itertools::tee::tee;
+
+/// https://more-itertools.readthedocs.io/en/v10.2.0/api.html#more_itertools.stagger
+///
+/// Yields tuples of items of `iter` at the given `offsets` from each position,
+/// e.g. offsets `[-1, 0, 1]` give (previous, current, next).
+pub fn stagger<T>(iter: Box<dyn Iterator<Item = Result<T,Error>>>, offsets: Vec<isize>, longest: bool, fill: T) -> Box<dyn Iterator<Item = Result<Vec<T>,Error>>>
+where T: Clone + 'static
+{
+    let mut children = Vec::with_capacity(offsets.len());
+    let mut rest = iter;
+    for _ in 1..offsets.len() {
+        let (child, next_rest) = tee(rest);
+        children.push(child);
+        rest = next_rest;
+    }
+    children.push(rest);
+
+    zip_offset(children, offsets, longest, fill)
+}
+
+#[cfg(test)]
+mod tests {
+    use crate::error;
+    use crate::utils::{extract_value_from_result_vec, generate_okok_iterator, generate_okokerr_iterator};
+
+    use super::*;
+
+    #[test]
+    fn test1() {
+        let ret = stagger(generate_okok_iterator(vec![0, 1, 2, 3]), vec![-1, 0, 1], false, -9);
+        assert_eq!(vec![vec![-9, 0, 1], vec![0, 1, 2], vec![1, 2, 3]], extract_value_from_result_vec(ret.collect::<Vec<_>>()).0);
+
+        let ret = stagger(generate_okok_iterator(vec![0, 1, 2, 3]), vec![0, 2], true, -9);
+        assert_eq!(vec![vec![0, 2], vec![1, 3], vec![2, -9], vec![3, -9]], extract_value_from_result_vec(ret.collect::<Vec<_>>()).0);
+    }
+
+    #[test]
+    fn test2_error() {
+        let ret = stagger(generate_okokerr_iterator(vec![0, 1, 2], error::overflow_error("[test]".to_string())), vec![0, 1], false, -9);
+        let ret = extract_value_from_result_vec(ret.collect::<Vec<_>>());
+        assert_eq!(vec![vec![0, 1], vec![1, 2]], ret.0);
+        assert_eq!(error::Kind::OverflowError, ret.1.unwrap().kind());
+    }
+
+    #[test]
+    fn test3_single_error() {
+        let v = vec![Ok(0), Ok(1), Ok(2), Ok(3), Err(error::overflow_error("[test]".to_string()))];
+        let ret = stagger(Box::new(v.into_iter()), vec![0, 1], false, -9);
+        let ret = extract_value_from_result_vec(ret.collect::<Vec<_>>());
+        assert_eq!(vec![vec![0, 1], vec![1, 2], vec![2, 3]], ret.0);
+        assert_eq!(error::Kind::OverflowError, ret.1.unwrap().kind());
+    }
+
+    #[test]
+    fn test4_reads_once() {
+        use std::cell::Cell;
+        use std::rc::Rc;
+
+        let reads = Rc::new(Cell::new(0));
+        let counter = Rc::clone(&reads);
+        let iter = Box::new((0..1000).map(Ok).inspect(move |_| counter.set(counter.get() + 1)));
+        assert_eq!(999, stagger(iter, vec![0, 1], false, -9).count());
+        assert!(reads.get() <= 1001);
+    }
+}
\ No newline at end of file
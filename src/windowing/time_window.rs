@@ -0,0 +1,304 @@
+use std::collections::{BTreeMap, VecDeque};
+
+use crate::error::{self, Error};
+
+/// Items whose timestamps fall in `[start, end)`, in arrival order.
+#[derive(Clone, Debug, PartialEq)]
+pub struct TimeWindow<T> {
+    pub start: i64,
+    pub end: i64,
+    pub items: Vec<T>
+}
+
+/// How the time windows treat items that arrive after their window was emitted.
+///
+/// A window is emitted once an item with a timestamp at or past its end
+/// (plus the waiting time of `Wait`) has been seen.
+#[derive(Clone, Copy, Debug, PartialEq)]
+pub enum Lateness {
+    /// Late items are discarded.
+    Drop,
+    /// A late item ends the stream with a `Kind::ValueError`.
+    Error,
+    /// Windows stay open until the timestamps go this far past their end,
+    /// so out-of-order items up to that delay are kept; later ones are discarded.
+    Wait(i64)
+}
+
+#[derive(Clone, Copy, Debug)]
+enum WindowKind {
+    Tumbling { width: i64 },
+    Hopping { width: i64, hop: i64 },
+    Session { gap: i64 }
+}
+
+impl WindowKind {
+    fn name(&self) -> &'static str {
+        match self {
+            WindowKind::Tumbling { .. } => "tumbling_window",
+            WindowKind::Hopping { .. } => "hopping_window",
+            WindowKind::Session { .. } => "session_window"
+        }
+    }
+
+    fn check(&self) -> Result<(), Error> {
+        let valid = match self {
+            WindowKind::Tumbling { width } => *width > 0,
+            WindowKind::Hopping { width, hop } => *width > 0 && *hop > 0,
+            WindowKind::Session { gap } => *gap > 0
+        };
+        if !valid {
+            return Err(error::value_error(format!("[{}] window sizes should be positive", self.name())));
+        }
+        Ok(())
+    }
+}
+
+struct TimeWindows<T> {
+    iter: Box<dyn Iterator<Item = Result<T,Error>>>,
+    ts_fn: fn(&T) -> i64,
+    kind: WindowKind,
+    lateness: Lateness,
+    // open windows by start: (end, items)
+    open: BTreeMap<i64, (i64, Vec<T>)>,
+    ready: VecDeque<TimeWindow<T>>,
+    // windows ending at or before the watermark are complete
+    watermark: i64,
+    initialized: bool,
+    iter_finished: bool
+}
+
+impl<T> TimeWindows<T>
+where T: Clone
+{
+    // Adds the item to its open windows; false if it is late for all of them.
+    fn assign(&mut self, ts: i64, item: T) -> bool {
+        match self.kind {
+            WindowKind::Tumbling { width } => {
+                let start = ts.div_euclid(width) * width;
+                self.add_to_window(start, start + width, item)
+            },
+            WindowKind::Hopping { width, hop } => {
+                let first = (ts - width).div_euclid(hop) + 1;
+                let last = ts.div_euclid(hop);
+                let mut assigned = false;
+                for k in first..=last {
+                    let start = k * hop;
+                    assigned |= self.add_to_window(start, start + width, item.clone());
+                }
+                assigned
+            },
+            WindowKind::Session { gap } => self.add_to_session(ts, ts + gap, item)
+        }
+    }
+
+    fn add_to_window(&mut self, start: i64, end: i64, item: T) -> bool {
+        if end <= self.watermark {
+            return false;
+        }
+        self.open.entry(start).or_insert_with(|| (end, Vec::new())).1.push(item);
+        true
+    }
+
+    // Merges [start, end) with the open sessions it overlaps.
+    fn add_to_session(&mut self, mut start: i64, mut end: i64, item: T) -> bool {
+        let overlapping = self.open.range(..end)
+            .filter(|(_, (e, _))| start < *e)
+            .map(|(s, _)| *s)
+            .collect::<Vec<_>>();
+
+        if overlapping.is_empty() && end <= self.watermark {
+            return false;
+        }
+
+        let mut items = Vec::new();
+        for s in overlapping {
+            let (e, mut v) = self.open.remove(&s).unwrap();
+            start = start.min(s);
+            end = end.max(e);
+            items.append(&mut v);
+        }
+        items.push(item);
+        self.open.insert(start, (end, items));
+        true
+    }
+
+    fn emit_closed(&mut self) {
+        while let Some(entry) = self.open.first_entry() {
+            if entry.get().0 > self.watermark {
+                break;
+            }
+            let start = *entry.key();
+            let (end, items) = entry.remove();
+            self.ready.push_back(TimeWindow { start, end, items });
+        }
+    }
+}
+
+impl<T> Iterator for TimeWindows<T>
+where T: Clone
+{
+    type Item = Result<TimeWindow<T>,Error>;
+
+    fn next(&mut self) -> Option<Self::Item> {
+        if !self.initialized {
+            self.initialized = true;
+            if let Err(err) = self.kind.check() {
+                self.iter_finished = true;
+                return Some(Err(err));
+            }
+        }
+
+        loop {
+            if let Some(window) = self.ready.pop_front() {
+                return Some(Ok(window));
+            }
+
+            if self.iter_finished {
+                return None;
+            }
+
+            match self.iter.next() {
+                None => {
+                    self.iter_finished = true;
+                    self.watermark = i64::MAX;
+                    self.emit_closed();
+                },
+                Some(Err(err)) => {
+                    self.iter_finished = true;
+                    self.open.clear();
+                    return Some(Err(err));
+                },
+                Some(Ok(item)) => {
+                    let ts = (self.ts_fn)(&item);
+                    if !self.assign(ts, item) && self.lateness == Lateness::Error {
+                        self.iter_finished = true;
+                        self.open.clear();
+                        return Some(Err(error::value_error(format!("[{}] late item at timestamp {}", self.kind.name(), ts))));
+                    }
+
+                    let wait = match self.lateness {
+                        Lateness::Wait(wait) => wait.max(0),
+                        _ => 0
+                    };
+                    self.watermark = self.watermark.max(ts.saturating_sub(wait));
+                    self.emit_closed();
+                }
+            }
+        }
+    }
+}
+
+fn time_windows<T>(iter: Box<dyn Iterator<Item = Result<T,Error>>>, ts_fn: fn(&T) -> i64, kind: WindowKind, lateness: Lateness) -> Box<dyn Iterator<Item = Result<TimeWindow<T>,Error>>>
+where T: Clone + 'static
+{
+    Box::new(TimeWindows {
+        iter,
+        ts_fn,
+        kind,
+        lateness,
+        open: BTreeMap::new(),
+        ready: VecDeque::new(),
+        watermark: i64::MIN,
+        initialized: false,
+        iter_finished: false
+    })
+}
+
+/// Groups items into consecutive, non-overlapping windows of `width` time
+/// units aligned on multiples of `width`, using the timestamps from `ts_fn`.
+/// Windows without items are not emitted.
+pub fn tumbling_window<T>(iter: Box<dyn Iterator<Item = Result<T,Error>>>, ts_fn: fn(&T) -> i64, width: i64, lateness: Lateness) -> Box<dyn Iterator<Item = Result<TimeWindow<T>,Error>>>
+where T: Clone + 'static
+{
+    time_windows(iter, ts_fn, WindowKind::Tumbling { width }, lateness)
+}
+
+/// Windows of `width` time units starting every `hop` units; with `hop < width`
+/// they overlap and an item belongs to several of them.
+pub fn hopping_window<T>(iter: Box<dyn Iterator<Item = Result<T,Error>>>, ts_fn: fn(&T) -> i64, width: i64, hop: i64, lateness: Lateness) -> Box<dyn Iterator<Item = Result<TimeWindow<T>,Error>>>
+where T: Clone + 'static
+{
+    time_windows(iter, ts_fn, WindowKind::Hopping { width, hop }, lateness)
+}
+
+/// Groups items into sessions separated by at least `gap` time units without
+/// items. A session spans from its first timestamp to its last one plus `gap`.
+pub fn session_window<T>(iter: Box<dyn Iterator<Item = Result<T,Error>>>, ts_fn: fn(&T) -> i64, gap: i64, lateness: Lateness) -> Box<dyn Iterator<Item = Result<TimeWindow<T>,Error>>>
+where T: Clone + 'static
+{
+    time_windows(iter, ts_fn, WindowKind::Session { gap }, lateness)
+}
+
+#[cfg(test)]
+mod tests {
+    use crate::utils::{extract_value_from_result_vec, generate_okok_iterator, generate_okokerr_iterator};
+
+    use super::*;
+
+    fn window(start: i64, end: i64, items: Vec<i64>) -> TimeWindow<i64> {
+        TimeWindow { start, end, items }
+    }
+
+    #[test]
+    fn test_tumbling() {
+        let ret = tumbling_window(generate_okok_iterator(vec![1, 3, 5, 12, 13, 31]), |x| *x, 5, Lateness::Drop);
+        assert_eq!(vec![window(0, 5, vec![1, 3]), window(5, 10, vec![5]), window(10, 15, vec![12, 13]), window(30, 35, vec![31])],
+            extract_value_from_result_vec(ret.collect::<Vec<_>>()).0);
+
+        let ret = tumbling_window(generate_okok_iterator(vec![-3, -1, 2]), |x| *x, 5, Lateness::Drop);
+        assert_eq!(vec![window(-5, 0, vec![-3, -1]), window(0, 5, vec![2])],
+            extract_value_from_result_vec(ret.collect::<Vec<_>>()).0);
+    }
+
+    #[test]
+    fn test_hopping() {
+        let ret = hopping_window(generate_okok_iterator(vec![1, 4, 6]), |x| *x, 4, 2, Lateness::Drop);
+        assert_eq!(vec![window(-2, 2, vec![1]), window(0, 4, vec![1]), window(2, 6, vec![4]), window(4, 8, vec![4, 6]), window(6, 10, vec![6])],
+            extract_value_from_result_vec(ret.collect::<Vec<_>>()).0);
+    }
+
+    #[test]
+    fn test_session() {
+        let ret = session_window(generate_okok_iterator(vec![1, 2, 4, 10, 11, 20]), |x| *x, 3, Lateness::Drop);
+        assert_eq!(vec![window(1, 7, vec![1, 2, 4]), window(10, 14, vec![10, 11]), window(20, 23, vec![20])],
+            extract_value_from_result_vec(ret.collect::<Vec<_>>()).0);
+
+        // an out-of-order item bridges two open sessions
+        let ret = session_window(generate_okok_iterator(vec![1, 7, 4, 20]), |x| *x, 4, Lateness::Wait(10));
+        assert_eq!(vec![window(1, 11, vec![1, 7, 4]), window(20, 24, vec![20])],
+            extract_value_from_result_vec(ret.collect::<Vec<_>>()).0);
+    }
+
+    #[test]
+    fn test_lateness() {
+        let v = vec![1, 6, 3, 11, 7];
+
+        let ret = tumbling_window(generate_okok_iterator(v.clone()), |x| *x, 5, Lateness::Drop);
+        assert_eq!(vec![window(0, 5, vec![1]), window(5, 10, vec![6]), window(10, 15, vec![11])],
+            extract_value_from_result_vec(ret.collect::<Vec<_>>()).0);
+
+        let ret = tumbling_window(generate_okok_iterator(v.clone()), |x| *x, 5, Lateness::Wait(2));
+        assert_eq!(vec![window(0, 5, vec![1, 3]), window(5, 10, vec![6, 7]), window(10, 15, vec![11])],
+            extract_value_from_result_vec(ret.collect::<Vec<_>>()).0);
+
+        let mut ret = tumbling_window(generate_okok_iterator(v), |x| *x, 5, Lateness::Error);
+        assert_eq!(Some(Ok(window(0, 5, vec![1]))), ret.next());
+        let err = ret.next().unwrap().err().unwrap();
+        assert_eq!(error::Kind::ValueError, err.kind());
+        assert_eq!("[tumbling_window] late item at timestamp 3", err.message().unwrap());
+        assert_eq!(None, ret.next());
+    }
+
+    #[test]
+    fn test_error() {
+        let mut ret = session_window(generate_okok_iterator(vec![1]), |x| *x, 0, Lateness::Drop);
+        assert_eq!(error::Kind::ValueError, ret.next().unwrap().err().unwrap().kind());
+        assert_eq!(None, ret.next());
+
+        let mut ret = tumbling_window(generate_okokerr_iterator(vec![1, 7], error::overflow_error("[test]".to_string())), |x| *x, 5, Lateness::Drop);
+        assert_eq!(Some(Ok(window(0, 5, vec![1]))), ret.next());
+        assert_eq!(error::Kind::OverflowError, ret.next().unwrap().err().unwrap().kind());
+        assert_eq!(None, ret.next());
+    }
+}
@@ -3,20 +3,66 @@ use std::fmt::Debug;
 use crate::error::Error;
 use crate::error;
 
+/// What `windowed` does with the last window when the input ends before
+/// filling it.
+#[derive(Clone, Debug, PartialEq)]
+pub enum WindowFill<T> {
+    /// Only full windows are emitted.
+    Drop,
+    /// The last window is completed with copies of the value.
+    Pad(T),
+    /// The last window is emitted shorter than `n`.
+    Partial
+}
+
 pub struct Windowed<T>
-where 
+where
 T: Clone + Debug + 'static
  {
+    // the last n items read
     buf: VecDeque<T>,
     iter: Box<dyn Iterator<Item=Result<T,Error>>>,
     n: usize,
     step: usize,
-    cache_first_window: bool,
+    fill: WindowFill<T>,
+    // items left to read before the next window is complete
+    countdown: usize,
     iter_finished: bool
 }
 
-impl<T> Iterator for Windowed<T> 
-where 
+impl<T> Windowed<T>
+where
+T: Clone + Debug + 'static
+{
+    // The trailing window made of the items read since the last emitted window, if any.
+    fn last_window(&self) -> Option<Vec<T>> {
+        let size = self.buf.len();
+        let keep = if size < self.n {
+            size
+        } else if 0 < self.countdown && self.countdown < self.step.min(self.n) {
+            self.n - self.countdown
+        } else {
+            return None;
+        };
+
+        if keep == 0 {
+            return None;
+        }
+
+        let mut ret = self.buf.iter().skip(size - keep).cloned().collect::<Vec<_>>();
+        match &self.fill {
+            WindowFill::Drop => None,
+            WindowFill::Pad(value) => {
+                ret.resize(self.n, value.clone());
+                Some(ret)
+            },
+            WindowFill::Partial => Some(ret)
+        }
+    }
+}
+
+impl<T> Iterator for Windowed<T>
+where
 T: Clone + Debug + 'static
 {
     type Item = Result<Vec<T>, Error>;
@@ -27,85 +73,58 @@ T: Clone + Debug + 'static
         }
 
         if self.n == 0 {
+            self.iter_finished = true;
             return Some(Err(error::value_error(String::from("[windowed:n should not be 0]"))));
         }
 
-        if !self.cache_first_window {
-            for _ in 0..self.n {
-                match self.iter.next() {
-                    Some(v) => {
-                        match v {
-                            Ok(ok_v) => {
-                                self.buf.push_back(ok_v);
-                            },
-                            Err(err_v) => { // upstream error
-                                self.iter_finished = true;
-                                return Some(Err(err_v));
-                            }
-                        }
-                        
-                    },
-                    None => {
-                        break;
-                    }
-                }
-            }
-            self.cache_first_window = true;
-
-            if self.buf.len() < self.n {
-                return None;
-            }
-            let mut ret = Vec::new();
-            for v in self.buf.iter() {
-                ret.push(v.clone());
-            }
-            return Some(Ok(ret));
+        if self.step == 0 {
+            self.iter_finished = true;
+            return Some(Err(error::value_error(String::from("[windowed:step should not be 0]"))));
         }
 
-        
-
-        for _ in 0..self.step {
-            self.buf.pop_front();
+        loop {
             match self.iter.next() {
-                Some(v) => {
-                    match v {
-                        Ok(ok_v) => {
-                            self.buf.push_back(ok_v);
-                        },
-                        Err(err_v) => { // upstream error
-                            self.iter_finished = true;
-                            return Some(Err(err_v));
-                        }
+                Some(Ok(v)) => {
+                    if self.buf.len() == self.n {
+                        self.buf.pop_front();
+                    }
+                    self.buf.push_back(v);
+
+                    self.countdown -= 1;
+                    if self.countdown == 0 {
+                        self.countdown = self.step;
+                        return Some(Ok(self.buf.iter().cloned().collect()));
                     }
                 },
+                Some(Err(err_v)) => { // upstream error
+                    self.iter_finished = true;
+                    return Some(Err(err_v));
+                },
                 None => {
-                    break;
+                    self.iter_finished = true;
+                    return self.last_window().map(Ok);
                 }
             }
         }
-
-        if self.buf.len() < self.n {
-            return None;
-        }
-        let mut ret = Vec::new();
-        for v in self.buf.iter() {
-            ret.push(v.clone());
-        }
-        return Some(Ok(ret));
     }
 }
 
 /// https://more-itertools.readthedocs.io/en/v10.2.0/api.html#more_itertools.windowed
-pub fn windowed<T>(iter: Box<dyn Iterator<Item=Result<T,Error>>>, n: usize, step: usize) -> Box<dyn Iterator<Item=Result<Vec<T>, Error>>> 
+///
+/// Windows of `n` items, each one starting `step` items after the previous
+/// one (items between windows are skipped when `step > n`). `fill` decides
+/// what becomes of the items left after the last full window.
+pub fn windowed<T>(iter: Box<dyn Iterator<Item=Result<T,Error>>>, n: usize, step: usize, fill: WindowFill<T>) -> Box<dyn Iterator<Item=Result<Vec<T>, Error>>>
 where
 T: Clone + Debug + 'static
 {
     Box::new(Windowed {
-        buf: VecDeque::new(),
+        buf: VecDeque::with_capacity(n),
         iter,
         n,
         step,
-        cache_first_window: false,
+        fill,
+        countdown: n,
         iter_finished: false
     })
 }
@@ -114,14 +133,14 @@ T: Clone + Debug + 'static
 mod tests {
     use std::vec;
 
-    use crate::utils::generate_okok_iterator;
+    use crate::utils::{extract_value_from_result_vec, generate_okok_iterator, generate_okokerr_iterator};
 
     use super::*;
 
     #[test]
     fn test1() {
         let v = vec![1,2,3,4,5];
-        let mut w = windowed(generate_okok_iterator(v), 3, 1);
+        let mut w = windowed(generate_okok_iterator(v), 3, 1, WindowFill::Drop);
 
         assert_eq!(vec![1,2,3], w.next().unwrap().ok().unwrap());
         assert_eq!(vec![2,3,4], w.next().unwrap().ok().unwrap());
@@ -132,7 +151,7 @@ mod tests {
     #[test]
     fn test2() {
         let v = vec![1,2,3,4,5,6,7,8];
-        let mut w = windowed(generate_okok_iterator(v), 3, 2);
+        let mut w = windowed(generate_okok_iterator(v), 3, 2, WindowFill::Drop);
         assert_eq!(vec![1,2,3], w.next().unwrap().ok().unwrap());
         assert_eq!(vec![3,4,5], w.next().unwrap().ok().unwrap());
         assert_eq!(vec![5,6,7], w.next().unwrap().ok().unwrap());
@@ -142,11 +161,61 @@ mod tests {
     #[test]
     fn test3() {
         let v = vec![1,2];
-        let mut w = windowed(generate_okok_iterator(v), 3, 1);
+        let mut w = windowed(generate_okok_iterator(v), 3, 1, WindowFill::Drop);
+        assert_eq!(None, w.next());
+
+        let v = vec![1,2];
+        let mut w = windowed(generate_okok_iterator(v), 0, 1, WindowFill::Drop);
+        assert_eq!(error::Kind::ValueError, w.next().unwrap().err().unwrap().kind());
         assert_eq!(None, w.next());
 
         let v = vec![1,2];
-        let mut w = windowed(generate_okok_iterator(v), 0, 1);
+        let mut w = windowed(generate_okok_iterator(v), 2, 0, WindowFill::Drop);
         assert_eq!(error::Kind::ValueError, w.next().unwrap().err().unwrap().kind());
     }
-}
\ No newline at end of file
+
+    #[test]
+    fn test4_fill() {
+        let v = vec![1,2,3,4,5,6,7,8];
+        let w = windowed(generate_okok_iterator(v.clone()), 3, 2, WindowFill::Pad(0));
+        assert_eq!(vec![vec![1,2,3], vec![3,4,5], vec![5,6,7], vec![7,8,0]], extract_value_from_result_vec(w.collect::<Vec<_>>()).0);
+
+        let w = windowed(generate_okok_iterator(v.clone()), 3, 2, WindowFill::Partial);
+        assert_eq!(vec![vec![1,2,3], vec![3,4,5], vec![5,6,7], vec![7,8]], extract_value_from_result_vec(w.collect::<Vec<_>>()).0);
+
+        let w = windowed(generate_okok_iterator(v), 4, 4, WindowFill::Pad(0));
+        assert_eq!(vec![vec![1,2,3,4], vec![5,6,7,8]], extract_value_from_result_vec(w.collect::<Vec<_>>()).0);
+
+        let w = windowed(generate_okok_iterator(vec![1,2]), 3, 1, WindowFill::Pad(0));
+        assert_eq!(vec![vec![1,2,0]], extract_value_from_result_vec(w.collect::<Vec<_>>()).0);
+
+        let w = windowed(generate_okok_iterator(vec![1,2]), 3, 1, WindowFill::Partial);
+        assert_eq!(vec![vec![1,2]], extract_value_from_result_vec(w.collect::<Vec<_>>()).0);
+
+        let w = windowed(generate_okok_iterator(Vec::<i32>::new()), 3, 1, WindowFill::Pad(0));
+        assert_eq!(0, w.count());
+    }
+
+    #[test]
+    fn test5_step_greater_than_n() {
+        let v = vec![1,2,3,4,5,6,7,8,9];
+        let w = windowed(generate_okok_iterator(v.clone()), 2, 3, WindowFill::Drop);
+        assert_eq!(vec![vec![1,2], vec![4,5], vec![7,8]], extract_value_from_result_vec(w.collect::<Vec<_>>()).0);
+
+        let w = windowed(generate_okok_iterator(v[..7].to_vec()), 2, 3, WindowFill::Pad(0));
+        assert_eq!(vec![vec![1,2], vec![4,5], vec![7,0]], extract_value_from_result_vec(w.collect::<Vec<_>>()).0);
+
+        // the trailing items all fall between windows
+        let w = windowed(generate_okok_iterator(v[..6].to_vec()), 2, 3, WindowFill::Pad(0));
+        assert_eq!(vec![vec![1,2], vec![4,5]], extract_value_from_result_vec(w.collect::<Vec<_>>()).0);
+    }
+
+    #[test]
+    fn test6_error() {
+        let mut w = windowed(generate_okokerr_iterator(vec![1,2,3], error::overflow_error("[test]".to_string())), 2, 1, WindowFill::Partial);
+        assert_eq!(Some(Ok(vec![1,2])), w.next());
+        assert_eq!(Some(Ok(vec![2,3])), w.next());
+        assert_eq!(error::Kind::OverflowError, w.next().unwrap().err().unwrap().kind());
+        assert_eq!(None, w.next());
+    }
+}